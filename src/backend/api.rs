@@ -7,9 +7,21 @@ use crate::{
     models::{
         billing::BillingEntry, // Includes PrincipalId, VaultId, SessionId, Timestamp, Role, VaultStatus, MemberStatus, etc.
         common::*,
-        payment::{PayMethod, PaymentSession},
-        vault_config::{UnlockConditions, VaultConfig}, // Use this for the actual member data
-        vault_invite_token::VaultInviteToken, // Import BillingEntry
+        payment::{E8s, PayMethod, PaymentSession},
+        vault_config::{ActivitySource, CoOwner, CoOwnerProposal, JointAction, JointActionRequest, OwnerPermission, OwnerRecoveryConfig, OwnerTransfer, PostUnlockPolicy, UnlockChallenge, UnlockConditions, VaultConfig}, // Use this for the actual member data
+        activity::{ActivityKind, ActivityRecord},
+        inactivity_warning::InactivityWarning,
+        notification::{NotificationEvent, NotificationId},
+        recovery_qr::RecoveryQrInfo,
+        owner_recovery::OwnerRebindRequest,
+        emergency_access::EmergencyAccessGrant,
+        disclosure::{DisclosureStage, DisclosureStageSpec},
+        receipt::{ContentReceipt, HeirReceiptReport, ReceiptSummary},
+        deletion::DeletionStatus,
+        setup::SetupChecklist,
+        attestation::{AttestationKind, EventAttestation, TrustedAuthority},
+        vault_content_item::{ContentVersionInfo, VaultContentItem},
+        vault_invite_token::{VaultInviteRequest, VaultInviteToken}, // Import BillingEntry
         vault_member::VaultMember, // Import PaymentSession & PayMethod directly
     },
     services::{
@@ -18,6 +30,20 @@ use crate::{
         scheduler_service,
         upload_service::{self, ContentId, FileMeta, UploadId}, // Import status struct
         vault_service::{self, VaultInitData, VaultUpdateData},
+        activity_service,
+        notification_service,
+        recovery_service,
+        ownership_service,
+        co_owner_service,
+        access_service::{self, ContentDownload, DownloadQuota},
+        disclosure_service,
+        content_service,
+        receipt_service,
+        deletion_service,
+        setup_service,
+        attestation_service,
+        succession_service,
+        challenge_service,
     },
     storage::{
         audit_logs::add_audit_log_entry, // Import storage helper
@@ -27,7 +53,7 @@ use crate::{
         vault_configs, // For list_billing
     },
     utils::{
-        guards::{self, admin_guard, check_admin, check_cycles, cron_or_admin_guard, member_guard, owner_guard, owner_or_heir_guard, role_guard, self_or_owner_guard}, // Import guards and named guards
+        guards::{self, admin_guard, check_admin, check_cycles, cron_guard, cron_or_admin_guard, member_guard, owner_guard, owner_or_heir_guard, role_guard, self_or_owner_guard}, // Import guards and named guards
         rate_limit::rate_guard, // Import the rate guard
     },
 };
@@ -52,15 +78,6 @@ thread_local! {
 
 // --- Guard Functions ---
 
-/// Checks if the caller is the designated admin principal.
-fn admin_guard() -> Result<(), VaultError> { // Return VaultError
-    let admin = crate::storage::config::get_admin_principal()?;
-    if api::caller() == admin {
-        Ok(())} else {
-        Err(VaultError::NotAuthorized("Caller is not admin.".to_string()))
-    }
-}
-
 /// Checks if the caller is the designated cron trigger OR the admin principal.
 fn cron_or_admin_guard() -> Result<(), VaultError> { // Return VaultError
     let caller = api::caller();
@@ -127,6 +144,26 @@ pub struct UpdateVaultRequest {
     pub unlock_conditions: Option<UnlockConditions>,
     #[validate(length(min = 1))]
     pub plan: Option<String>,
+    pub activity_source: Option<ActivitySource>,
+    pub owner_recovery: Option<OwnerRecoveryConfig>,
+    #[validate(range(max = 90))]
+    pub restore_window_days: Option<u32>, // 0 deletes immediately
+    #[validate(range(max = 20))]
+    pub max_content_versions: Option<u8>,
+    #[validate(range(max = 365))]
+    pub successor_activation_days: Option<u32>, // 0 disables automatic successor activation
+    #[validate(range(max = 90))]
+    pub challenge_window_days: Option<u32>, // 0 disables unlock challenges
+}
+
+// Vault Details (name and description only; owners and managers)
+#[derive(CandidType, Deserialize, Clone, Debug, Validate)]
+pub struct UpdateVaultDetailsRequest {
+    pub vault_id: VaultId, // Candid only decodes well-formed principals
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>, // None clears it
 }
 
 // Generate Invite
@@ -137,6 +174,19 @@ pub struct GenerateInviteRequest {
     pub role: Role,
 }
 
+// Invite Successor
+#[derive(CandidType, Deserialize, Clone, Debug, Validate)]
+pub struct InviteSuccessorRequest {
+    pub vault_id: VaultId, // Candid only decodes well-formed principals
+    pub heir: PrincipalId, // The heir (or dormant successor) to back up
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(min = 1, max = 100))]
+    pub relation: Option<String>,
+    #[validate(email)]
+    pub email: String,
+}
+
 // Claim Invite
 #[derive(CandidType, Deserialize, Clone, Debug, Validate)]
 pub struct ClaimInviteRequest {
@@ -198,6 +248,14 @@ pub struct TriggerUnlockRequest {
     // Add any necessary witness data/proof if needed
 }
 
+// Recovery QR
+#[derive(CandidType, Deserialize, Clone, Debug, Validate)]
+pub struct RedeemRecoveryQrRequest {
+    pub vault_id: VaultId, // Candid only decodes well-formed principals
+    #[validate(length(min = 64, max = 64))]
+    pub secret: String,
+}
+
 // Admin & Listing
 #[derive(CandidType, Deserialize, Validate)]
 pub struct ListRequest {
@@ -228,6 +286,7 @@ pub struct VaultSummary {
      pub storage_used_bytes: u64,
      pub plan: String,
      pub created_at: Timestamp,
+     pub receipts: Option<ReceiptSummary>, // Post-unlock delivery progress, once the vault is unlockable
 }
 
 // Define the response type for get_metrics including dynamic cycle balance
@@ -303,6 +362,44 @@ fn get_payment_status(session_id: SessionId) -> Result<PaymentSessionStatus, Vau
    payment_service::get_payment_session_status(&session_id)
 }
 
+// --- Renewal Endpoints ---
+
+#[derive(CandidType, Deserialize, Clone, Debug, Validate)]
+pub struct RenewVaultRequest {
+    pub vault_id: VaultId, // Candid only decodes well-formed principals
+    #[validate(range(min = 1, max = 10))]
+    pub years: u32,
+}
+
+/// Quotes the price (e8s) of extending the vault's term by `years`.
+#[query]
+async fn quote_renewal(vault_id: VaultId, years: u32) -> Result<E8s, VaultError> {
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    vault_service::quote_renewal(&vault_id, years)
+}
+
+/// Starts a renewal payment. Callable by owners with settings permission, managers and the
+/// designated renewal payer; the term is extended once the payment is verified with `verify_payment`.
+#[update]
+async fn renew_vault(req: RenewVaultRequest) -> Result<PaymentSession, VaultError> {
+    validate_request(&req)?;
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::start_renewal(&req.vault_id, req.years, caller).await
+        .inspect(|_| activity_service::note_owner_update(&req.vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Designates (or clears, with `None`) who besides the owner may pay for renewals.
+#[update]
+async fn set_renewal_payer(vault_id: VaultId, payer: Option<PrincipalId>) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::set_renewal_payer(&vault_id, payer, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
 // --- Vault Core Endpoints ---
 
 #[update]
@@ -331,6 +428,25 @@ async fn get_vault(vault_id: VaultId) -> Result<VaultConfig, VaultError> {
     vault_service::get_vault_config(&vault_id)
 }
 
+/// Status, usage and post-unlock delivery progress of a vault.
+#[query]
+async fn get_vault_summary(vault_id: VaultId) -> Result<VaultSummary, VaultError> {
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    guards::owner_or_heir_guard(vault_id).map_err(VaultError::NotAuthorized)?;
+    let config = vault_service::get_vault_config(&vault_id).await?;
+    let receipts = matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked)
+        .then(|| receipt_service::summarize_receipts(&config));
+    Ok(VaultSummary {
+        vault_id: config.vault_id,
+        owner: config.owner,
+        status: config.status,
+        storage_used_bytes: config.storage_used_bytes,
+        plan: config.plan,
+        created_at: config.created_at,
+        receipts,
+    })
+}
+
 #[update(guard = "owner_guard")]
 async fn update_vault(req: UpdateVaultRequest) -> Result<Option<PaymentSession>, VaultError> {
     validate_request(&req)?;
@@ -344,11 +460,292 @@ async fn update_vault(req: UpdateVaultRequest) -> Result<Option<PaymentSession>,
         description: req.description,
         unlock_conditions: req.unlock_conditions,
         plan: req.plan,
+        activity_source: req.activity_source,
+        owner_recovery: req.owner_recovery,
+        restore_window_days: req.restore_window_days,
+        max_content_versions: req.max_content_versions,
+        successor_activation_days: req.successor_activation_days,
+        challenge_window_days: req.challenge_window_days,
     };
 
     vault_service::update_vault_config(&req.vault_id, update_data, caller).await
 }
 
+/// Updates a vault's name and description. Open to managers, who cannot use `update_vault`.
+#[update]
+async fn update_vault_details(req: UpdateVaultDetailsRequest) -> Result<(), VaultError> {
+    validate_request(&req)?;
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    guards::owner_or_manager_guard(req.vault_id).map_err(VaultError::NotAuthorized)?;
+    vault_service::update_vault_details(&req.vault_id, req.name, req.description, caller)
+        .inspect(|_| activity_service::note_owner_update(&req.vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Moves a vault to the trash, or starts purging it right away if it has no restore window.
+/// Purging runs in the background; poll `get_deletion_status`.
+#[update]
+async fn delete_vault(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::delete_vault(&vault_id, caller).await
+}
+
+/// Takes a vault out of the trash while its restore window is open.
+#[update]
+async fn restore_vault(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::restore_vault(&vault_id, caller).await
+}
+
+/// Progress of a vault deletion, or its tombstone once finished. Owner or admin.
+#[query]
+async fn get_deletion_status(vault_id: VaultId) -> Result<DeletionStatus, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    deletion_service::get_deletion_status(&vault_id, caller)
+}
+
+// --- Setup Progression Endpoints ---
+
+/// What the vault still needs before it leaves setup. Owners only.
+#[query]
+async fn get_setup_checklist(vault_id: VaultId) -> Result<SetupChecklist, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    setup_service::get_setup_checklist(&vault_id, caller)
+}
+
+/// Finalises setup once content and unlock rules are in place. The vault becomes Active
+/// as soon as the remaining checklist items (claimed heir, witnesses) are met. Returns the new status.
+#[update]
+async fn finalize_setup(vault_id: VaultId) -> Result<VaultStatus, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    setup_service::finalize_setup(&vault_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+// --- Authority Attestation Endpoints ---
+
+/// Submits a trusted authority's attestation (e.g. of the owner's death) with the hash of its evidence.
+#[update]
+async fn attest_event(vault_id: VaultId, kind: AttestationKind, evidence_hash: String) -> Result<EventAttestation, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    attestation_service::attest_event(&vault_id, kind, evidence_hash, caller).await
+}
+
+/// Attestations filed for a vault. Owners, members and admin.
+#[query]
+async fn get_attestations(vault_id: VaultId) -> Result<Vec<EventAttestation>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    attestation_service::get_attestations(&vault_id, caller)
+}
+
+// --- Ownership Transfer Endpoints ---
+
+/// Proposes handing the vault to `new_owner`. Takes effect once they call `accept_owner_transfer`.
+#[update]
+async fn propose_owner_transfer(vault_id: VaultId, new_owner: PrincipalId) -> Result<OwnerTransfer, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    ownership_service::propose_owner_transfer(&vault_id, new_owner, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn accept_owner_transfer(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    ownership_service::accept_owner_transfer(&vault_id, caller)
+}
+
+/// Withdraws (owner) or declines (proposed owner) a pending transfer.
+#[update]
+async fn cancel_owner_transfer(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    ownership_service::cancel_owner_transfer(&vault_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+// --- Co-owner / Joint Approval Endpoints ---
+
+/// Offers co-ownership to a principal. Primary owner only; takes effect once accepted.
+#[update]
+async fn propose_co_owner(vault_id: VaultId, principal: PrincipalId, permissions: Vec<OwnerPermission>) -> Result<CoOwnerProposal, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::propose_co_owner(&vault_id, principal, permissions, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Accepts a co-ownership offered to the caller.
+#[update]
+async fn accept_co_owner(vault_id: VaultId) -> Result<CoOwner, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::accept_co_owner(&vault_id, caller)
+}
+
+/// Withdraws (primary owner) or declines (invitee) a co-ownership offer.
+#[update]
+async fn cancel_co_owner_proposal(vault_id: VaultId, principal: PrincipalId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::cancel_co_owner_proposal(&vault_id, principal, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Removes a co-owner (primary owner), or leaves the vault (the co-owner themselves).
+#[update]
+async fn remove_co_owner(vault_id: VaultId, principal: PrincipalId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::remove_co_owner(&vault_id, principal, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn set_co_owner_permissions(vault_id: VaultId, principal: PrincipalId, permissions: Vec<OwnerPermission>) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::set_co_owner_permissions(&vault_id, principal, permissions, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn set_joint_approval(vault_id: VaultId, enabled: bool) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::set_joint_approval(&vault_id, enabled, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn propose_joint_action(vault_id: VaultId, action: JointAction) -> Result<JointActionRequest, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::propose_joint_action(&vault_id, action, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Approves a joint action; executes it once every owner has approved.
+#[update]
+async fn approve_joint_action(vault_id: VaultId, action_id: u64) -> Result<Option<PaymentSession>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::approve_joint_action(&vault_id, action_id, caller).await
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn cancel_joint_action(vault_id: VaultId, action_id: u64) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    co_owner_service::cancel_joint_action(&vault_id, action_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[query]
+async fn get_pending_joint_actions(vault_id: VaultId) -> Result<Vec<JointActionRequest>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    co_owner_service::get_pending_joint_actions(&vault_id, caller)
+}
+
+// --- Owner Rebind Endpoints (lost principal recovery) ---
+
+/// Filed by the owner from their new principal, or by a heir or witness on their behalf.
+#[update]
+async fn request_owner_rebind(vault_id: VaultId, new_owner: PrincipalId) -> Result<OwnerRebindRequest, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    ownership_service::request_owner_rebind(&vault_id, new_owner, caller)
+}
+
+#[update]
+async fn attest_owner_rebind(vault_id: VaultId) -> Result<OwnerRebindRequest, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    ownership_service::attest_owner_rebind(&vault_id, caller)
+}
+
+/// Lets the current owner principal reject a rebind during the veto window.
+#[update]
+async fn veto_owner_rebind(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    ownership_service::veto_owner_rebind(&vault_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn finalize_owner_rebind(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    ownership_service::finalize_owner_rebind(&vault_id, caller)
+}
+
+#[query]
+async fn get_owner_rebind_request(vault_id: VaultId) -> Result<Option<OwnerRebindRequest>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    ownership_service::get_owner_rebind_request(&vault_id, caller)
+}
+
+// --- Owner Activity Endpoints ---
+
+/// Proof-of-life call: resets the inactivity timer on every vault the caller owns.
+/// Returns the number of vaults touched.
+#[update]
+async fn heartbeat() -> Result<u32, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    activity_service::heartbeat(caller)
+}
+
+/// Returns the recent owner activity that reset the vault's inactivity timer (newest first).
+#[query]
+async fn get_owner_activity(vault_id: VaultId) -> Result<Vec<ActivityRecord>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    activity_service::get_activity_history(&vault_id, caller)
+}
+
+/// Returns the inactivity warnings issued for the vault (newest first). Owner only.
+#[query]
+async fn get_inactivity_warnings(vault_id: VaultId) -> Result<Vec<InactivityWarning>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    activity_service::get_inactivity_warnings(&vault_id, caller)
+}
+
 // --- Invitation & Member Endpoints ---
 
 #[update]
@@ -404,6 +801,37 @@ async fn revoke_invite(token_id: InviteTokenId /* Principal */) -> Result<(), Va
     Err(VaultError::NotImplemented("Revoke invite endpoint not implemented".to_string()))
 }
 
+/// Sends the notification for a pending invite again. Owners and managers.
+#[update]
+async fn resend_invite(token_id: InviteTokenId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    invite_service::invite_service::resend_invite(token_id, caller)
+}
+
+/// Invites a dormant successor for a heir. Returns the invite token and the heir's Shamir share.
+#[update]
+async fn invite_successor(req: InviteSuccessorRequest) -> Result<(InviteTokenId, Vec<u8>), VaultError> {
+    validate_request(&req)?;
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    let invite = VaultInviteRequest { name: req.name, relation: req.relation, email: req.email };
+    succession_service::invite_successor(&req.vault_id, req.heir, invite, caller).await
+        .inspect(|_| activity_service::note_owner_update(&req.vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Activates a heir's dormant successor in the heir's place. Owners only.
+#[update]
+async fn activate_successor(vault_id: VaultId, heir: PrincipalId) -> Result<VaultMember, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    succession_service::activate_successor(&vault_id, heir, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
 // --- Content Upload Endpoints ---
 
 #[update]
@@ -454,6 +882,197 @@ async fn request_download(req: RequestDownloadRequest) -> Result<DownloadInfo, V
     Err(VaultError::NotImplemented("Request download endpoint not implemented".to_string()))
 }
 
+/// Returns a content item to an owner or an authorised member, with the member's remaining daily quota.
+#[update]
+async fn download_content(vault_id: VaultId, content_id: ContentId) -> Result<ContentDownload, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    access_service::download_content(&vault_id, &content_id, caller)
+}
+
+#[query]
+async fn get_download_quota(vault_id: VaultId) -> Result<DownloadQuota, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    access_service::get_download_quota(&vault_id, caller)
+}
+
+#[update]
+async fn set_post_unlock_policy(vault_id: VaultId, policy: PostUnlockPolicy) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    access_service::set_post_unlock_policy(&vault_id, policy, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Overrides a member's daily download limit, or resets it to the vault default with `None`.
+#[update]
+async fn set_member_download_limit(vault_id: VaultId, member: PrincipalId, limit: Option<u8>) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    access_service::set_member_download_limit(&vault_id, member, limit, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Sets (or clears, with `release_at: None`) a content item's time-capsule release date and recipients.
+#[update]
+async fn set_time_capsule(vault_id: VaultId, content_id: ContentId, release_at: Option<Timestamp>, recipients: Vec<PrincipalId>) -> Result<VaultContentItem, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    content_service::set_time_capsule(&vault_id, &content_id, release_at, recipients, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::ContentEdit))
+}
+
+/// Moves a content item to the trash until the vault's restore window ends.
+#[update]
+async fn delete_content(vault_id: VaultId, content_id: ContentId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    content_service::delete_content(&vault_id, &content_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::ContentEdit))
+}
+
+/// Takes a content item out of the trash while its restore window is open.
+#[update]
+async fn restore_content(vault_id: VaultId, content_id: ContentId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    content_service::restore_content(&vault_id, &content_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::ContentEdit))
+}
+
+/// Replaces a content item's payload; the previous payload is kept as a version. Returns the new version.
+#[update]
+async fn update_content(vault_id: VaultId, content_id: ContentId, payload: Vec<u8>) -> Result<u32, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    content_service::update_content_payload(&vault_id, &content_id, payload, caller)
+}
+
+/// Makes a retained version of a content item current again. Returns the new version.
+#[update]
+async fn rollback_content(vault_id: VaultId, content_id: ContentId, version: u32) -> Result<u32, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    content_service::rollback_content(&vault_id, &content_id, version, caller)
+}
+
+#[query]
+async fn list_content_versions(vault_id: VaultId, content_id: ContentId) -> Result<Vec<ContentVersionInfo>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    content_service::list_content_versions(&vault_id, &content_id, caller)
+}
+
+// --- Receipt Endpoints ---
+
+/// Confirms the caller received a released content item.
+#[update]
+async fn acknowledge_receipt(vault_id: VaultId, content_id: ContentId) -> Result<ContentReceipt, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    receipt_service::acknowledge_receipt(&vault_id, &content_id, caller)
+}
+
+#[update]
+async fn set_receipt_witness(vault_id: VaultId, witness: Option<PrincipalId>) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    receipt_service::set_receipt_witness(&vault_id, witness, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Who has downloaded and acknowledged what (owner or receipt witness).
+#[query]
+async fn get_completion_report(vault_id: VaultId) -> Result<Vec<HeirReceiptReport>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    receipt_service::get_completion_report(&vault_id, caller)
+}
+
+// --- Emergency Access Endpoints ---
+
+/// Adds or updates (`wait_days`) an emergency contact, or removes it (`None`).
+#[update]
+async fn set_emergency_contact(vault_id: VaultId, member: PrincipalId, wait_days: Option<u32>) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    access_service::set_emergency_contact(&vault_id, member, wait_days, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn request_emergency_access(vault_id: VaultId, content_ids: Vec<ContentId>) -> Result<EmergencyAccessGrant, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    access_service::request_emergency_access(&vault_id, content_ids, caller)
+}
+
+#[update]
+async fn reject_emergency_access(vault_id: VaultId, grant_id: u64) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    access_service::reject_emergency_access(&vault_id, grant_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn revoke_emergency_access(vault_id: VaultId, grant_id: u64) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    access_service::revoke_emergency_access(&vault_id, grant_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[query]
+async fn get_emergency_access_grants(vault_id: VaultId) -> Result<Vec<EmergencyAccessGrant>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    access_service::get_emergency_access_grants(&vault_id, caller)
+}
+
+// --- Disclosure Stage Endpoints ---
+
+/// Replaces the vault's disclosure stages (before unlock). Unstaged content is released with the vault.
+#[update]
+async fn set_disclosure_stages(vault_id: VaultId, stages: Vec<DisclosureStageSpec>) -> Result<Vec<DisclosureStage>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    disclosure_service::set_disclosure_stages(&vault_id, stages, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Witness confirmation for a stage that requires one.
+#[update]
+async fn confirm_disclosure_stage(vault_id: VaultId, stage_id: u32) -> Result<DisclosureStage, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    disclosure_service::confirm_disclosure_stage(&vault_id, stage_id, caller)
+}
+
+#[query]
+async fn get_disclosure_stages(vault_id: VaultId) -> Result<Vec<DisclosureStage>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    disclosure_service::get_disclosure_stages(&vault_id, caller)
+}
+
 // --- Unlock Endpoint ---
 #[update(guard = "owner_or_heir_guard")]
 async fn trigger_unlock(req: TriggerUnlockRequest) -> Result<(), VaultError> {
@@ -475,8 +1094,97 @@ async fn trigger_unlock(req: TriggerUnlockRequest) -> Result<(), VaultError> {
     Ok({})
 }
 
+/// Records the caller's (heir or witness) approval of the unlock. Returns the resulting vault status.
+#[update]
+async fn approve_unlock(vault_id: VaultId) -> Result<VaultStatus, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::approve_unlock(&vault_id, caller).await
+}
+
+/// Sets how much a heir's or witness's approval counts toward weighted unlock thresholds (1 to 10).
+#[update]
+async fn set_member_approval_weight(vault_id: VaultId, member: PrincipalId, weight: u32) -> Result<VaultMember, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::set_member_approval_weight(&vault_id, member, weight, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+/// Challenges the unlock within the vault's challenge window. Member downloads pause until resolved.
+#[update]
+async fn challenge_unlock(vault_id: VaultId, reason: String) -> Result<UnlockChallenge, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    challenge_service::challenge_unlock(&vault_id, reason, caller)
+}
+
+/// Confirms a challenged unlock as a witness who had not approved it, resuming downloads.
+#[update]
+async fn confirm_challenged_unlock(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    challenge_service::confirm_challenged_unlock(&vault_id, caller)
+}
+
+// --- Recovery QR Endpoints ---
+
+/// Issues a new recovery secret (replacing any previous one). The secret is only returned here.
+#[update]
+async fn issue_recovery_qr(vault_id: VaultId) -> Result<String, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    recovery_service::issue_recovery_qr(&vault_id, caller).await
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[update]
+async fn revoke_recovery_qr(vault_id: VaultId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    recovery_service::revoke_recovery_qr(&vault_id, caller)
+        .inspect(|_| activity_service::note_owner_update(&vault_id, caller, ActivityKind::VaultUpdate))
+}
+
+#[query]
+async fn get_recovery_qr(vault_id: VaultId) -> Result<Option<RecoveryQrInfo>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    recovery_service::get_recovery_qr_info(&vault_id, caller)
+}
+
+#[update]
+async fn redeem_recovery_qr(req: RedeemRecoveryQrRequest) -> Result<(), VaultError> {
+    validate_request(&req)?;
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    recovery_service::redeem_recovery_qr(&req.vault_id, &req.secret, caller).await
+}
+
 // --- Maintenance Endpoint ---
 
+/// Hands outbox events to the off-chain relay. Events not acknowledged within the
+/// redelivery timeout are returned again by a later pull.
+#[update(guard = "cron_guard")]
+async fn pull_notifications(after: Option<NotificationId>, limit: Option<u32>) -> Result<Vec<NotificationEvent>, VaultError> {
+    check_cycles()?;
+    Ok(notification_service::pull_notifications(after, limit.unwrap_or(50) as usize))
+}
+
+/// Confirms that the relay delivered the given events. Returns the number acknowledged.
+#[update(guard = "cron_guard")]
+async fn ack_notifications(ids: Vec<NotificationId>) -> Result<u32, VaultError> {
+    check_cycles()?;
+    notification_service::ack_notifications(ids)
+}
+
 #[update(guard = "cron_or_admin_guard")] // Use named guard
 async fn daily_maintenance() -> Result<(), VaultError> { // Return VaultError
     cron_or_admin_guard()?;
@@ -506,6 +1214,73 @@ async fn list_vaults(req: ListRequest) -> Result<ListVaultsResponse, VaultError>
     Err(VaultError::NotImplemented("List vaults endpoint not implemented".to_string()))
 }
 
+/// Registers (or renames) a trusted authority whose attestations count toward unlock.
+#[update(guard = "admin_guard")]
+async fn add_trusted_authority(principal: PrincipalId, name: String) -> Result<TrustedAuthority, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    attestation_service::add_trusted_authority(principal, name, caller)
+}
+
+/// Removes a trusted authority; its attestations no longer count.
+#[update(guard = "admin_guard")]
+async fn remove_trusted_authority(principal: PrincipalId) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    attestation_service::remove_trusted_authority(principal, caller)
+}
+
+#[query(guard = "admin_guard")]
+async fn list_trusted_authorities() -> Result<Vec<TrustedAuthority>, VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    attestation_service::list_trusted_authorities(caller)
+}
+
+/// Freezes a disputed vault: no status changes, deletion, unlock or downloads until released.
+/// Metadata stays readable. The reason is recorded in the vault's audit log.
+#[update(guard = "admin_guard")]
+async fn set_legal_hold(vault_id: VaultId, reason: String) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::set_legal_hold(&vault_id, reason, caller).await
+}
+
+/// Lifts the legal hold of a vault. Scheduler jobs pick the vault up again on their next run.
+#[update(guard = "admin_guard")]
+async fn release_legal_hold(vault_id: VaultId, reason: String) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    vault_service::release_legal_hold(&vault_id, reason, caller).await
+}
+
+/// Clears an open unlock challenge after review, resuming member downloads. The note is audited.
+#[update(guard = "admin_guard")]
+async fn clear_unlock_challenge(vault_id: VaultId, note: String) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    challenge_service::clear_unlock_challenge(&vault_id, note, caller)
+}
+
+/// Sets how many days an unpaid Draft vault is kept before the scheduler removes it. 0 disables expiry.
+#[update(guard = "admin_guard")]
+async fn set_draft_expiry_days(days: u32) -> Result<(), VaultError> {
+    let caller = api::caller();
+    rate_guard().map_err(VaultError::RateLimitExceeded)?;
+    check_cycles()?;
+    if days > 365 {
+        return Err(VaultError::InvalidInput("Draft expiry cannot exceed 365 days".to_string()));
+    }
+    crate::storage::config::set_draft_expiry_days(days);
+    ic_cdk::print(format!("INFO: Draft expiry set to {} days by {}", days, caller));
+    Ok(())
+}
+
 #[query(guard = "admin_guard")] // Use named guard
 async fn list_billing(req: ListRequest) -> Result<ListBillingResponse, VaultError> { // Return VaultError
     validate_request(&req)?;
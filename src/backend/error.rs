@@ -113,6 +113,21 @@ pub enum VaultError {
 
     #[error("Storage quota exceeded for vault: {0}, quota: {1}, used: {2}, diff: {3}")]
     StorageQuotaExceeded(String, u64, u64, u64),
+
+    #[error("Change requires approval from all owners: {0}")]
+    JointApprovalRequired(String),
+
+    #[error("Daily download limit reached: {0}")]
+    DownloadLimitReached(String),
+
+    #[error("Post-unlock access period has ended: {0}")]
+    AccessPeriodEnded(String),
+
+    #[error("Vault is under legal hold: {0}")]
+    LegalHold(String),
+
+    #[error("Vault unlock is challenged: {0}")]
+    UnlockChallenged(String),
 }
 
 impl std::fmt::Display for VaultError {
@@ -154,6 +169,11 @@ impl std::fmt::Display for VaultError {
             VaultError::ChecksumMismatch => write!(f, "Checksum mismatch during upload finalization"),
             VaultError::StorageQuotaExceeded(id, quota, used, diff) =>
                 write!(f, "Storage quota exceeded for vault: {}, quota: {}, used: {}, diff: {}", id, quota, used, diff),
+            VaultError::JointApprovalRequired(s) => write!(f, "Change requires approval from all owners: {}", s),
+            VaultError::DownloadLimitReached(s) => write!(f, "Daily download limit reached: {}", s),
+            VaultError::AccessPeriodEnded(s) => write!(f, "Post-unlock access period has ended: {}", s),
+            VaultError::LegalHold(s) => write!(f, "Vault is under legal hold: {}", s),
+            VaultError::UnlockChallenged(s) => write!(f, "Vault unlock is challenged: {}", s),
         }
     }
 } 
\ No newline at end of file
@@ -45,6 +45,9 @@ fn init(args: InitArgs) {
         args.cron_principal,
         args.min_cycles_threshold
     );
+    if let Some(days) = args.draft_expiry_days {
+        storage_config::set_draft_expiry_days(days);
+    }
 
     _restart_rng();
 
@@ -55,6 +58,15 @@ fn init(args: InitArgs) {
 #[ic_cdk::post_upgrade]
 fn post_upgrade() {
     _restart_rng();
+    // Timers do not survive an upgrade; pick up vault deletions that were in progress
+    if let Err(e) = services::deletion_service::resume_deletion_jobs(ic_cdk::api::time()) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to resume vault deletion jobs: {:?}", e);
+    }
+    storage::notifications::rebuild_outstanding_index();
+    storage::notifications::rebuild_vault_index();
+    storage::tokens::rebuild_vault_index();
+    storage::time_capsules::rebuild_vault_index();
+    storage::content_trash::rebuild_vault_index();
     ic_cdk::println!("LiVault backend canister upgraded.");
     // Post-upgrade logic, e.g., migrating stable memory
 }
@@ -20,6 +20,10 @@ pub struct VaultMetrics {
     pub invites_sent_total: u64,
     pub invites_claimed_total: u64,
     pub unlock_triggers_total: u64,
+    #[serde(default)]
+    pub drafts_expired_total: u64,            // Unpaid Draft vaults removed by the scheduler
+    #[serde(default)]
+    pub payment_sessions_expired_total: u64,  // Payment sessions timed out before confirmation
     // Add other relevant metrics as needed
     pub scheduler_last_run_success: Option<u64>, // Timestamp of last successful run
 }
@@ -37,6 +41,8 @@ impl Default for VaultMetrics {
             invites_sent_total: 0,
             invites_claimed_total: 0,
             unlock_triggers_total: 0,
+            drafts_expired_total: 0,
+            payment_sessions_expired_total: 0,
             scheduler_last_run_success: None,
         }
     }
@@ -0,0 +1,19 @@
+// src/backend/models/activity.rs
+use crate::models::common::Timestamp;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Kind of owner action that may reset the inactivity (dead-man's switch) timer.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum ActivityKind {
+    Heartbeat,   // Explicit proof-of-life call
+    VaultUpdate, // Any owner update call on the vault (settings, plan, members)
+    ContentEdit, // Content uploaded or modified by the owner
+}
+
+/// A single owner activity that was counted towards resetting the inactivity timer.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ActivityRecord {
+    pub timestamp: Timestamp,
+    pub kind: ActivityKind,
+}
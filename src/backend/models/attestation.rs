@@ -0,0 +1,30 @@
+// src/backend/models/attestation.rs
+use crate::models::common::{PrincipalId, Timestamp, VaultId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// The event an authority attests to.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttestationKind {
+    Death,
+}
+
+/// A notary, registry or institution whose attestations count toward unlock. Managed by admin.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct TrustedAuthority {
+    pub principal: PrincipalId,
+    pub name: String,
+    pub added_by: PrincipalId,
+    pub added_at: Timestamp,
+}
+
+/// An authority's statement that an event concerning the vault owner has happened.
+/// Only the hash of the supporting evidence is kept on-chain.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EventAttestation {
+    pub vault_id: VaultId,
+    pub authority: PrincipalId,
+    pub kind: AttestationKind,
+    pub evidence_hash: String, // Hex SHA-256 of the signed evidence document
+    pub attested_at: Timestamp,
+}
@@ -16,6 +16,19 @@ pub struct AuditLogEntry {
     pub vault_id: String,
 }
 
+impl AuditLogEntry {
+    /// Creates an entry; `timestamp` and `vault_id` are filled in by `storage::audit_logs::add_entry`.
+    pub fn new(action: LogAction, actor: Principal, details: Option<String>) -> Self {
+        Self {
+            timestamp: 0,
+            actor,
+            action,
+            details,
+            vault_id: String::new(),
+        }
+    }
+}
+
 /// Enum representing the different types of actions that can be logged.
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub enum LogAction {
@@ -35,6 +48,60 @@ pub enum LogAction {
     InviteClaimed,
     InviteRevoked,
     PaymentVerified,
+    RenewalPayerChanged,
     MaintenanceRun,
+    RecoveryQrIssued,
+    RecoveryQrRevoked,
+    RecoveryQrRedeemed,
+    RecoveryQrFailedAttempt,
+    RecoveryQrRejected,
+    OwnerTransferProposed,
+    OwnerTransferCancelled,
+    OwnerTransferred,
+    OwnerTransferAccepted,
+    OwnerRebindRequested,
+    OwnerRebindAttested,
+    OwnerRebindApproved,
+    OwnerRebindVetoed,
+    OwnerRebindExpired,
+    OwnerRebound,
+    CoOwnerProposed,
+    CoOwnerProposalCancelled,
+    CoOwnerAdded,
+    CoOwnerRemoved,
+    CoOwnerPermissionsChanged,
+    JointApprovalChanged,
+    JointActionProposed,
+    JointActionApproved,
+    JointActionCancelled,
+    JointActionExecuted,
+    JointActionFailed,
+    EmergencyContactChanged,
+    EmergencyAccessRequested,
+    EmergencyAccessRejected,
+    EmergencyAccessActivated,
+    EmergencyAccessRevoked,
+    DisclosureStagesUpdated,
+    DisclosureStageConfirmed,
+    DisclosureStageReleased,
+    TimeCapsuleScheduled,
+    TimeCapsuleReleased,
+    AccessPolicyChanged,
+    ContentReceiptAcknowledged,
+    ReceiptWitnessChanged,
+    ContentRestored,
+    VaultRestored,
+    ContentUpdated,
+    ContentRolledBack,
+    SetupCompleted,
+    VaultActivated,
+    LegalHoldPlaced,
+    LegalHoldReleased,
+    EventAttested,
+    SuccessorInvited,
+    SuccessorActivated,
+    ApprovalWeightChanged,
+    UnlockChallenged,
+    UnlockChallengeResolved,
     // Add more actions as needed
 } 
\ No newline at end of file
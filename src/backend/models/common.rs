@@ -37,6 +37,7 @@ pub enum Role {
     Master,
     Heir,
     Witness,
+    Manager, // Delegated helper: keeps settings up to date; no Shamir share, content or unlock rights
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
@@ -60,6 +61,7 @@ pub enum MemberStatus {
     Verified, // Member confirmed/verified (e.g., after claiming invite)
     Active,
     Revoked, // Access revoked by master
+    Dormant, // Designated successor, takes no part until activated in place of their heir
 }
 
 // TODO: Define specific storage plan tiers if needed 
\ No newline at end of file
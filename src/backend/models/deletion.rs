@@ -0,0 +1,92 @@
+// src/backend/models/deletion.rs
+use crate::models::common::{PrincipalId, Timestamp, VaultId, VaultStatus};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// The store a deletion job is currently purging. Steps run in declaration order;
+/// the vault config goes last so the vault stays visible (as Deleted) until everything else is gone.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeletionStep {
+    Uploads,           // Upload sessions and their chunks
+    Content,           // Content items (payload blobs)
+    ContentVersions,   // Retained previous payloads
+    ContentIndex,
+    TimeCapsules,
+    ContentTrash,
+    Receipts,
+    EmergencyAccess,
+    Tokens,
+    Members,
+    Approvals,
+    Attestations,
+    Activity,
+    InactivityWarnings,
+    RecoveryQr,
+    OwnerRebind,
+    Notifications,     // Outbox events with their dedupe, outstanding and vault index entries
+    AuditLogs,
+    Config,            // Vault config, metrics and tombstone
+}
+
+impl DeletionStep {
+    /// The step after this one, or `None` once the config step is done.
+    pub fn next(self) -> Option<DeletionStep> {
+        use DeletionStep::*;
+        match self {
+            Uploads => Some(Content),
+            Content => Some(ContentVersions),
+            ContentVersions => Some(ContentIndex),
+            ContentIndex => Some(TimeCapsules),
+            TimeCapsules => Some(ContentTrash),
+            ContentTrash => Some(Receipts),
+            Receipts => Some(EmergencyAccess),
+            EmergencyAccess => Some(Tokens),
+            Tokens => Some(Members),
+            Members => Some(Approvals),
+            Approvals => Some(Attestations),
+            Attestations => Some(Activity),
+            Activity => Some(InactivityWarnings),
+            InactivityWarnings => Some(RecoveryQr),
+            RecoveryQr => Some(OwnerRebind),
+            OwnerRebind => Some(Notifications),
+            Notifications => Some(AuditLogs),
+            AuditLogs => Some(Config),
+            Config => None,
+        }
+    }
+}
+
+/// A vault deletion in progress. Persisted after every batch so it can resume after a trap or upgrade.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct DeletionJob {
+    pub vault_id: VaultId,
+    pub owner: PrincipalId,
+    pub requested_by: PrincipalId,
+    pub previous_status: VaultStatus, // Status before the vault was marked Deleted (for metrics)
+    pub step: DeletionStep,
+    pub records_removed: u64,
+    pub bytes_freed: u64,             // Content payload bytes removed so far
+    pub started_at: Timestamp,
+    pub updated_at: Timestamp,
+}
+
+/// The record left behind once a vault is fully deleted.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct VaultTombstone {
+    pub vault_id: VaultId,
+    pub owner: PrincipalId,
+    pub deleted_by: PrincipalId,
+    pub plan: String,
+    pub created_at: Timestamp,  // Vault creation time
+    pub requested_at: Timestamp,
+    pub completed_at: Timestamp,
+    pub records_removed: u64,
+    pub bytes_freed: u64,
+}
+
+/// Progress of a vault deletion, as returned by the API.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub enum DeletionStatus {
+    InProgress(DeletionJob),
+    Completed(VaultTombstone),
+}
@@ -0,0 +1,30 @@
+// src/backend/models/disclosure.rs
+use crate::models::common::{ContentId, PrincipalId, Timestamp};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum DisclosureStageStatus {
+    Locked,     // Vault not unlockable yet, or the stage's own conditions are unmet
+    Unlockable, // Stage content is released to heirs
+}
+
+/// An owner-defined disclosure stage, as submitted via `set_disclosure_stages`.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisclosureStageSpec {
+    pub name: String,
+    pub content_ids: Vec<ContentId>,
+    pub delay_days: u32,                      // Days after the vault becomes unlockable
+    pub required_witness_confirmations: u32,  // Witnesses who must confirm before release
+}
+
+/// A disclosure stage of a vault with its release state. Content not assigned to any
+/// stage is released together with the vault.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DisclosureStage {
+    pub stage_id: u32, // 1-based position in the vault's stage list
+    pub spec: DisclosureStageSpec,
+    pub witness_confirmations: Vec<PrincipalId>,
+    pub status: DisclosureStageStatus,
+    pub released_at: Option<Timestamp>,
+}
@@ -0,0 +1,32 @@
+// src/backend/models/emergency_access.rs
+use crate::models::common::{ContentId, PrincipalId, Timestamp, VaultId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum EmergencyAccessStatus {
+    Pending,  // Waiting period running, owner can still reject
+    Active,   // Waiting period elapsed, grantee can download the scoped items
+    Rejected, // Rejected by an owner during the waiting period
+    Revoked,  // Withdrawn by an owner (or the grantee) after activation
+}
+
+/// A member's request for early access to specific content items, independent of the vault unlock.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct EmergencyAccessGrant {
+    pub grant_id: u64, // Request timestamp, unique per vault
+    pub vault_id: VaultId,
+    pub grantee: PrincipalId,
+    pub content_ids: Vec<ContentId>,
+    pub status: EmergencyAccessStatus,
+    pub requested_at: Timestamp,
+    pub available_at: Timestamp, // End of the waiting period
+    pub resolved_at: Option<Timestamp>, // When it was activated, rejected or revoked
+}
+
+impl EmergencyAccessGrant {
+    /// True if the grant currently lets the grantee read `content_id`.
+    pub fn covers(&self, grantee: &PrincipalId, content_id: &ContentId) -> bool {
+        self.status == EmergencyAccessStatus::Active && self.grantee == *grantee && self.content_ids.contains(content_id)
+    }
+}
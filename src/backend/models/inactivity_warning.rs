@@ -0,0 +1,24 @@
+// src/backend/models/inactivity_warning.rs
+use crate::models::common::{Timestamp, VaultId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Who an inactivity warning is addressed to.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum InactivityWarningKind {
+    OwnerReminder, // Deadline approaching, owner can still reset it
+    HeirNotice,    // Deadline passed, the inactivity condition has fired
+}
+
+/// A warning issued by the scheduler for one crossing of an inactivity threshold.
+/// (`deadline`, `days_before`, `kind`) identifies the crossing; a new owner activity
+/// moves the deadline and starts a fresh set of crossings.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct InactivityWarning {
+    pub vault_id: VaultId,
+    pub kind: InactivityWarningKind,
+    pub deadline: Timestamp,   // When the inactivity condition fires (nanoseconds)
+    pub days_before: u32,      // Threshold that was crossed (0 for the heir notice)
+    pub issued_at: Timestamp,
+}
+
@@ -7,4 +7,5 @@ pub struct InitArgs {
     pub admin_principal: Principal,
     pub cron_principal: Principal,
     pub min_cycles_threshold: u128,
+    pub draft_expiry_days: Option<u32>, // Days before an unpaid Draft vault is removed (default 7, 0 = never)
 } 
\ No newline at end of file
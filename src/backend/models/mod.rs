@@ -6,6 +6,17 @@ pub mod vault_content_item;
 pub mod payment;
 pub mod billing;
 pub mod audit_log;
+pub mod activity;
+pub mod inactivity_warning;
+pub mod notification;
+pub mod recovery_qr;
+pub mod owner_recovery;
+pub mod emergency_access;
+pub mod disclosure;
+pub mod receipt;
+pub mod deletion;
+pub mod setup;
+pub mod attestation;
 // pub mod api_types; // Potential future module for API-specific structs
 // Add other models as needed, e.g., for metrics, logs
 
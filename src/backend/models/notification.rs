@@ -0,0 +1,66 @@
+// src/backend/models/notification.rs
+use crate::models::attestation::AttestationKind;
+use crate::models::common::{ContentId, InviteTokenId, PrincipalId, Role, Timestamp, VaultId, VaultStatus};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub type NotificationId = u64;
+
+/// What happened; the relay picks the template from this.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum NotificationKind {
+    InviteCreated { token_id: InviteTokenId, role: Role, expires_at: Timestamp },
+    InviteExpiring { token_id: InviteTokenId, expires_at: Timestamp },
+    UnlockRequested { requested_by: PrincipalId },
+    EventAttested { authority: PrincipalId, kind: AttestationKind },
+    SuccessorActivated { heir: PrincipalId, successor: PrincipalId },
+    UnlockChallenged { raised_by: PrincipalId, reason: String },
+    UnlockChallengeResolved { resolved_by: PrincipalId },
+    VaultExpiring { expires_at: Timestamp },
+    DraftExpiring { expires_at: Timestamp },
+    GracePeriodStarted { status: VaultStatus },
+    PaymentConfirmed { session_id: PrincipalId, amount_e8s: u64 },
+    InactivityWarning { days_remaining: u32, deadline: Timestamp },
+    InactivityThresholdReached { deadline: Timestamp },
+    OwnerTransferProposed { from: PrincipalId, expires_at: Timestamp },
+    OwnerChanged { from: PrincipalId, to: PrincipalId },
+    OwnerRebindRequested { new_owner: PrincipalId },
+    OwnerRebindApproved { new_owner: PrincipalId, executable_at: Timestamp },
+    CoOwnerProposed { proposed_by: PrincipalId, expires_at: Timestamp },
+    JointActionProposed { action_id: u64, proposed_by: PrincipalId, expires_at: Timestamp },
+    EmergencyAccessRequested { grant_id: u64, grantee: PrincipalId, available_at: Timestamp },
+    EmergencyAccessGranted { grant_id: u64 },
+    EmergencyAccessRejected { grant_id: u64 },
+    DisclosureStageReleased { stage_id: u32, name: String },
+    TimeCapsuleReleased { content_id: ContentId, title: Option<String> },
+}
+
+/// Who the relay should deliver to. Principals are resolved to contact details off-chain.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub enum NotificationRecipient {
+    Principal(PrincipalId),
+    Email(String),
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum NotificationStatus {
+    Pending,   // Not yet handed to the relay
+    Delivered, // Pulled by the relay, waiting for an ack
+    Acked,     // Relay confirmed delivery
+    Failed,    // Gave up after the maximum number of delivery attempts
+}
+
+/// An event in the notification outbox.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct NotificationEvent {
+    pub id: NotificationId,
+    pub vault_id: VaultId,
+    pub kind: NotificationKind,
+    pub recipient: NotificationRecipient,
+    pub dedupe_key: String, // Same key is only ever enqueued once
+    pub created_at: Timestamp,
+    pub status: NotificationStatus,
+    pub delivery_attempts: u32,
+    pub last_delivered_at: Option<Timestamp>,
+    pub acked_at: Option<Timestamp>,
+}
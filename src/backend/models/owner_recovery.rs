@@ -0,0 +1,35 @@
+// src/backend/models/owner_recovery.rs
+use crate::models::common::{PrincipalId, Timestamp, VaultId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum OwnerRebindStatus {
+    Pending,   // Collecting heir/witness attestations
+    Approved,  // Quorum reached, waiting out the veto delay
+    Completed, // Owner rebound to the new principal
+    Vetoed,    // Rejected by the current owner principal
+    Expired,   // Quorum not reached in time
+}
+
+/// A request to rebind a vault's owner to a new principal, filed by the owner from that principal.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct OwnerRebindRequest {
+    pub vault_id: VaultId,
+    pub previous_owner: PrincipalId,
+    pub new_owner: PrincipalId,
+    pub status: OwnerRebindStatus,
+    pub requested_at: Timestamp,
+    pub heir_attestations: Vec<PrincipalId>,
+    pub witness_attestations: Vec<PrincipalId>,
+    pub approved_at: Option<Timestamp>,
+    pub executable_at: Option<Timestamp>, // End of the veto window
+    pub resolved_at: Option<Timestamp>,
+}
+
+impl OwnerRebindRequest {
+    /// True while the request can still be attested, vetoed or executed.
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, OwnerRebindStatus::Pending | OwnerRebindStatus::Approved)
+    }
+}
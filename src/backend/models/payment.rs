@@ -1,7 +1,7 @@
 // src/backend/models/payment.rs
 // Placeholder for PaymentSession struct and related payment models 
 
-use crate::models::common::{PrincipalId, Timestamp};
+use crate::models::common::{PrincipalId, Timestamp, VaultId};
 use crate::error::VaultError;
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
@@ -30,7 +30,8 @@ pub enum PayState {
 pub enum PaymentPurpose {
     InitialVaultCreation,
     PlanUpgrade { new_plan: String },
-    // Add other purposes as needed, e.g., VaultRenewal
+    VaultRenewal { vault_id: VaultId, years: u32 }, // Bound to the vault it was priced for
+    // Add other purposes as needed
 }
 
 impl Default for PayMethod { fn default() -> Self { PayMethod::IcpDirect } }
@@ -122,6 +123,49 @@ where
     })
 }
 
+/// True if `principal` started a session for `purpose` that can still be paid (Issued/Pending, not expired).
+pub fn has_live_payment_session(principal: &PrincipalId, purpose: &PaymentPurpose, current_time: Timestamp) -> bool {
+    PAYMENT_SESSIONS.with(|map| {
+        map.borrow().values().any(|session| {
+            session.initiating_principal == *principal
+                && session.purpose == *purpose
+                && matches!(session.state, PayState::Issued | PayState::Pending)
+                && !session.is_expired(current_time)
+        })
+    })
+}
+
+/// Marks every Issued/Pending session past its expiry as Expired. Returns the sessions that were expired.
+pub fn expire_payment_sessions(current_time: Timestamp) -> Vec<PaymentSession> {
+    PAYMENT_SESSIONS.with(|map| {
+        let mut expired = Vec::new();
+        for session in map.borrow_mut().values_mut() {
+            if session.is_expired(current_time) {
+                session.state = PayState::Expired;
+                session.error_message = Some("Session expired before payment was confirmed.".to_string());
+                expired.push(session.clone());
+            }
+        }
+        expired
+    })
+}
+
+/// Removes finished sessions (Closed, Expired or Error) whose expiry or close time is before `cutoff`.
+/// Returns the number of sessions removed.
+pub fn purge_payment_sessions(cutoff: Timestamp) -> usize {
+    PAYMENT_SESSIONS.with(|map| {
+        let mut map = map.borrow_mut();
+        let before = map.len();
+        map.retain(|_, session| match session.state {
+            PayState::Closed | PayState::Expired | PayState::Error => {
+                session.closed_at.unwrap_or(session.expires_at) >= cutoff
+            }
+            PayState::Issued | PayState::Pending | PayState::Confirmed => true,
+        });
+        before - map.len()
+    })
+}
+
 // --- Payment Initialization Struct (from API) ---
 #[derive(Clone, Debug, CandidType, serde::Deserialize)]
 pub struct PaymentInitRequest {
@@ -0,0 +1,43 @@
+// src/backend/models/receipt.rs
+use crate::models::common::{ContentId, PrincipalId, Timestamp};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// What one member has done with one content item.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ContentReceipt {
+    pub content_id: ContentId,
+    pub first_downloaded_at: Option<Timestamp>,
+    pub last_downloaded_at: Option<Timestamp>,
+    pub download_count: u32,
+    pub acknowledged_at: Option<Timestamp>, // Member confirmed they received the item
+}
+
+/// Per-item line of the completion report.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ItemReceiptStatus {
+    pub content_id: ContentId,
+    pub title: Option<String>,
+    pub downloaded_at: Option<Timestamp>,
+    pub download_count: u32,
+    pub acknowledged_at: Option<Timestamp>,
+}
+
+/// Completion report section for one heir: every item released to them and its receipt state.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct HeirReceiptReport {
+    pub heir: PrincipalId,
+    pub name: Option<String>,
+    pub items: Vec<ItemReceiptStatus>,
+    pub complete: bool, // Every released item acknowledged
+}
+
+/// Counts shown in the vault summary.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, Default)]
+pub struct ReceiptSummary {
+    pub heirs_total: u32,
+    pub heirs_complete: u32,
+    pub items_released: u32,     // Summed over heirs
+    pub items_downloaded: u32,
+    pub items_acknowledged: u32,
+}
@@ -0,0 +1,38 @@
+// src/backend/models/recovery_qr.rs
+use crate::models::common::{PrincipalId, Timestamp, VaultId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum RecoveryQrStatus {
+    Active,
+    Revoked,  // By the owner or on first member join
+    Redeemed, // Single use
+}
+
+/// The issued recovery secret for a vault. Only the SHA-256 of the secret is stored;
+/// the secret itself is returned once at issuance.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct RecoveryQrRecord {
+    pub vault_id: VaultId,
+    pub secret_hash: String, // Hex-encoded SHA-256 of the secret
+    pub status: RecoveryQrStatus,
+    pub issued_at: Timestamp,
+    pub failed_attempts: u32, // Total across all callers (informational)
+    pub revoked_at: Option<Timestamp>,
+    pub redeemed_at: Option<Timestamp>,
+    pub redeemed_by: Option<PrincipalId>,
+    #[serde(default)]
+    pub attempts_since_lockout: u32, // Wrong secrets from any caller since the last lockout
+    #[serde(default)]
+    pub locked_until: Option<Timestamp>, // No redemptions before this; wrong secrets never revoke the QR
+}
+
+/// What the owner sees about the current recovery QR (never the secret).
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct RecoveryQrInfo {
+    pub status: RecoveryQrStatus,
+    pub issued_at: Timestamp,
+    pub failed_attempts: u32,
+    pub locked_until: Option<Timestamp>,
+}
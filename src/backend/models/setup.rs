@@ -0,0 +1,39 @@
+// src/backend/models/setup.rs
+use crate::models::common::{VaultId, VaultStatus};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+/// Something a vault still needs before it can leave setup.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetupRequirement {
+    ContentItem,       // At least one content item
+    ClaimedHeir,       // At least one heir has claimed their invite
+    UnlockConditions,  // At least one unlock condition is configured
+    Witness,           // Enough claimed witnesses (and witness weight) for the required witness approvals
+}
+
+/// Setup progress of a vault, as returned by `get_setup_checklist`.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct SetupChecklist {
+    pub vault_id: VaultId,
+    pub status: VaultStatus,
+    pub has_content: bool,
+    pub has_claimed_heir: bool,
+    pub unlock_conditions_configured: bool,
+    pub witness_required: bool,
+    pub has_claimed_witness: bool,
+    pub missing: Vec<SetupRequirement>, // Empty once the checklist is satisfied
+}
+
+impl SetupChecklist {
+    /// Whether every requirement is met.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Whether the owner-side requirements (content and unlock rules) are met,
+    /// which is enough for the owner to finalise setup while invites are still pending.
+    pub fn owner_ready(&self) -> bool {
+        self.has_content && self.unlock_conditions_configured
+    }
+}
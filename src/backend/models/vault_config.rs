@@ -1,5 +1,8 @@
 // src/backend/models/vault_config.rs
-use crate::models::common::{PrincipalId, Timestamp, VaultId, VaultStatus};
+use crate::models::activity::ActivityKind;
+use crate::models::common::{ContentId, PrincipalId, Timestamp, VaultId, VaultStatus};
+use crate::models::disclosure::{DisclosureStage, DisclosureStageSpec, DisclosureStageStatus};
+use crate::error::VaultError;
 use candid::{CandidType, Principal};
 use serde::{Deserialize, Serialize};
 
@@ -20,6 +23,248 @@ pub struct VaultConfig {
     pub expires_at: Timestamp, // Calculated at creation (e.g., 10 years)
     pub unlocked_at: Option<Timestamp>,
     pub last_accessed_by_owner: Option<Timestamp>, // Track owner activity
+    #[serde(default)]
+    pub activity_source: ActivitySource, // Which owner actions reset the inactivity timer
+    #[serde(default)]
+    pub renewal_payer: Option<PrincipalId>, // Besides the owner, who may pay to renew the term
+    #[serde(default)]
+    pub pending_owner_transfer: Option<OwnerTransfer>, // Proposed by the owner, awaiting acceptance
+    #[serde(default)]
+    pub owner_recovery: Option<OwnerRecoveryConfig>, // Unset: owner principal cannot be rebound by members
+    #[serde(default)]
+    pub co_owners: Vec<CoOwner>, // Joint owners besides `owner` (the primary owner)
+    #[serde(default)]
+    pub pending_co_owners: Vec<CoOwnerProposal>, // Proposed by the primary owner, awaiting the invitee's acceptance
+    #[serde(default)]
+    pub require_joint_approval: bool, // Unlock rules, plan changes and deletion need every owner's sign-off
+    #[serde(default)]
+    pub pending_joint_actions: Vec<JointActionRequest>,
+    #[serde(default)]
+    pub emergency_contacts: Vec<EmergencyContact>, // Members allowed to request emergency access
+    #[serde(default)]
+    pub disclosure_stages: Vec<DisclosureStage>, // Released independently once the vault is unlockable
+    #[serde(default)]
+    pub unlockable_at: Option<Timestamp>, // When the vault last became Unlockable (stage delays count from here)
+    #[serde(default)]
+    pub post_unlock: PostUnlockPolicy,
+    #[serde(default)]
+    pub receipt_witness: Option<PrincipalId>, // Witness allowed to view the post-unlock completion report
+    #[serde(default = "default_restore_window_days")]
+    pub restore_window_days: u32, // How long deleted content and the vault itself stay restorable; 0 deletes immediately
+    #[serde(default)]
+    pub soft_deleted: Option<SoftDeletion>, // Set while the vault is Deleted but still restorable
+    #[serde(default = "default_max_content_versions")]
+    pub max_content_versions: u8, // Previous versions retained per content item; they count toward storage
+    #[serde(default)]
+    pub legal_hold: Option<LegalHold>, // Set by admin; freezes transitions, deletion, unlock and downloads
+    #[serde(default)]
+    pub successor_activation_days: u32, // Days an heir may ignore an unlock request before their successor takes over; 0 disables
+    #[serde(default)]
+    pub unlock_requested_at: Option<Timestamp>, // First unlock request since the owner was last active
+    #[serde(default = "default_challenge_window_days")]
+    pub challenge_window_days: u32, // How long after becoming Unlockable the unlock can be challenged; 0 disables
+    #[serde(default)]
+    pub unlock_challenge: Option<UnlockChallenge>, // Open challenge; pauses member downloads until resolved
+}
+
+pub const DEFAULT_RESTORE_WINDOW_DAYS: u32 = 30;
+pub const DEFAULT_MAX_CONTENT_VERSIONS: u8 = 5;
+pub const DEFAULT_CHALLENGE_WINDOW_DAYS: u32 = 14;
+
+fn default_restore_window_days() -> u32 {
+    DEFAULT_RESTORE_WINDOW_DAYS
+}
+
+fn default_max_content_versions() -> u8 {
+    DEFAULT_MAX_CONTENT_VERSIONS
+}
+
+fn default_challenge_window_days() -> u32 {
+    DEFAULT_CHALLENGE_WINDOW_DAYS
+}
+
+/// A vault deletion that can still be undone. The deletion job starts once the restore window ends.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SoftDeletion {
+    pub deleted_at: Timestamp,
+    pub deleted_by: PrincipalId,
+    pub previous_status: VaultStatus, // Status the vault returns to on restore
+    #[serde(default)]
+    pub unlocked_at: Option<Timestamp>, // Cleared by the move to Deleted; put back on restore
+}
+
+/// An admin freeze placed on a disputed vault. Metadata stays readable; everything that changes
+/// the vault's state or releases content is refused until the hold is released.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct LegalHold {
+    pub reason: String,
+    pub placed_by: PrincipalId, // Admin principal
+    pub placed_at: Timestamp,
+}
+
+/// An objection raised against an unlock by a member or the owner. Member downloads are paused
+/// until an admin clears it or a witness who had not approved the unlock confirms it.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct UnlockChallenge {
+    pub raised_by: PrincipalId,
+    pub reason: String,
+    pub raised_at: Timestamp,
+}
+
+/// Limits on member downloads once the vault is unlocked (`post_unlock` in the plan JSON).
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct PostUnlockPolicy {
+    pub access_until: Option<u64>, // Epoch seconds, as in the plan JSON. No member downloads after this; the vault expires
+    pub daily_download_limit: u8,  // Default per-member limit; 0 means unlimited
+}
+
+impl Default for PostUnlockPolicy {
+    fn default() -> Self {
+        Self { access_until: None, daily_download_limit: 3 }
+    }
+}
+
+impl PostUnlockPolicy {
+    /// `access_until` in nanoseconds, for comparison with `ic_cdk::api::time()`.
+    pub fn access_until_nanos(&self) -> Option<Timestamp> {
+        self.access_until.map(|sec| sec.saturating_mul(1_000_000_000))
+    }
+
+    /// Whether the access period has ended at `current_time` (nanoseconds).
+    pub fn access_ended(&self, current_time: Timestamp) -> bool {
+        self.access_until_nanos().map_or(false, |t| current_time > t)
+    }
+}
+
+/// A member the owner allows to request emergency access, and how long they must wait.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct EmergencyContact {
+    pub member: PrincipalId,
+    pub wait_days: u32, // Waiting period before a request is granted unless an owner rejects it
+}
+
+/// What a co-owner may do besides viewing the vault. The primary owner can do everything.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum OwnerPermission {
+    ManageSettings, // Vault settings, unlock rules, plan, renewal
+    ManageMembers,  // Invites and member management
+    ManageContent,  // Uploads and content edits
+}
+
+/// What a vault manager (`Role::Manager`) may do on the owners' behalf. Managers can never change
+/// unlock rules, read content or delete the vault, and they hold no Shamir share.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum ManagerPermission {
+    EditDetails,         // Vault name and description
+    ManageInvites,       // Revoke pending heir and witness invites (issuing hands out shares: owners only)
+    ResendNotifications, // Re-send invite notifications
+    RenewPlan,           // Start renewal payments
+}
+
+impl ManagerPermission {
+    /// The owner permission that lets a co-owner do the same.
+    pub fn owner_permission(self) -> OwnerPermission {
+        match self {
+            ManagerPermission::EditDetails | ManagerPermission::RenewPlan => OwnerPermission::ManageSettings,
+            ManagerPermission::ManageInvites | ManagerPermission::ResendNotifications => OwnerPermission::ManageMembers,
+        }
+    }
+}
+
+/// A joint owner of a vault.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CoOwner {
+    pub principal: PrincipalId,
+    pub permissions: Vec<OwnerPermission>,
+    pub added_at: Timestamp,
+    pub last_active_at: Option<Timestamp>, // This co-owner's proof of life
+}
+
+/// A co-ownership offered by the primary owner. Takes effect when `principal` accepts it.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CoOwnerProposal {
+    pub principal: PrincipalId,
+    pub permissions: Vec<OwnerPermission>,
+    pub proposed_at: Timestamp,
+    pub expires_at: Timestamp,
+}
+
+/// A change that needs every owner's sign-off when `require_joint_approval` is set.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum JointAction {
+    UpdateUnlockConditions(UnlockConditions),
+    ChangePlan(String),
+    DeleteVault,
+    RemoveCoOwner(PrincipalId),
+    DisableJointApproval,
+    SetDisclosureStages(Vec<DisclosureStageSpec>),
+    SetApprovalWeight { member: PrincipalId, weight: u32 },
+}
+
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct JointActionRequest {
+    pub id: u64, // Proposal timestamp, unique per vault
+    pub action: JointAction,
+    pub proposed_by: PrincipalId,
+    pub approvals: Vec<PrincipalId>, // Includes the proposer
+    pub expires_at: Timestamp,
+}
+
+/// How co-owner activity combines for the inactivity unlock condition.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum InactivityMode {
+    AllOwners, // Fires only once every owner has been inactive
+    AnyOwner,  // Fires as soon as one owner has been inactive
+}
+
+impl Default for InactivityMode {
+    fn default() -> Self {
+        InactivityMode::AllOwners
+    }
+}
+
+/// Quorum and delay for rebinding the owner to a new principal (lost device recovery).
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct OwnerRecoveryConfig {
+    pub required_heir_attestations: u32,
+    pub required_witness_attestations: u32,
+    /// Time (seconds) between quorum and rebind, during which the old principal can veto.
+    pub delay_sec: u64,
+}
+
+/// An ownership transfer proposed by the current owner. Takes effect when `new_owner` accepts it.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct OwnerTransfer {
+    pub new_owner: PrincipalId,
+    pub proposed_at: Timestamp,
+    pub expires_at: Timestamp,
+}
+
+/// Selects which owner actions count as activity for the inactivity unlock condition.
+/// An explicit heartbeat always counts, whatever the setting.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Copy)]
+pub enum ActivitySource {
+    AnyUpdate,     // Heartbeats, vault updates and content edits
+    HeartbeatOnly, // Only explicit heartbeat calls
+    ContentEdits,  // Heartbeats and content edits
+}
+
+impl Default for ActivitySource {
+    fn default() -> Self {
+        ActivitySource::AnyUpdate
+    }
+}
+
+impl ActivitySource {
+    /// Returns true if an action of the given kind resets the inactivity timer.
+    pub fn counts(&self, kind: ActivityKind) -> bool {
+        match (self, kind) {
+            (_, ActivityKind::Heartbeat) => true,
+            (ActivitySource::AnyUpdate, _) => true,
+            (ActivitySource::ContentEdits, ActivityKind::ContentEdit) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Defines the conditions required to unlock a vault.
@@ -33,8 +278,55 @@ pub struct UnlockConditions {
     pub required_heir_approvals: Option<u32>,
     /// Number of witness approvals required.
     pub required_witness_approvals: Option<u32>,
-    // TODO: Add field for recovery QR configuration if needed
-    // pub recovery_qr_config: Option<RecoveryQrConfig>,
+    /// Days before the inactivity deadline at which the owner is reminded (e.g. [30, 14, 7, 1]).
+    /// Falls back to the scheduler default when unset.
+    #[serde(default)]
+    pub inactivity_warning_days: Option<Vec<u32>>,
+    /// Recovery QR settings. No QR can be issued while unset or disabled.
+    #[serde(default)]
+    pub recovery_qr: Option<RecoveryQrConfig>,
+    /// For co-owned vaults: whether all owners or any owner must be inactive.
+    #[serde(default)]
+    pub inactivity_mode: InactivityMode,
+    /// Number of distinct trusted authorities that must attest the owner's death.
+    #[serde(default)]
+    pub required_death_attestations: Option<u32>,
+    /// Sum of `approval_weight` over approving heirs required. Checked alongside the heir and
+    /// witness counts above; all configured approval thresholds must be met together.
+    #[serde(default)]
+    pub required_heir_approval_weight: Option<u32>,
+    /// Sum of `approval_weight` over approving witnesses required.
+    #[serde(default)]
+    pub required_witness_approval_weight: Option<u32>,
+    /// Other vaults in this canister that must reach a given state first. Acts as a gate on the
+    /// conditions above; with no other condition configured, the dependencies alone unlock the vault.
+    #[serde(default)]
+    pub depends_on_vaults: Vec<VaultDependency>,
+}
+
+/// The state a linked vault must have reached.
+#[derive(CandidType, Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DependencyState {
+    Unlockable, // Unlockable or Unlocked
+    Unlocked,   // Rejected for now: no transition to Unlocked exists yet
+}
+
+/// Unlock condition leaf: "vault X is Unlockable/Unlocked". Only vaults the dependent vault's
+/// owner also owns can be referenced, and dependencies may not form a cycle.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct VaultDependency {
+    pub vault_id: VaultId,
+    pub required_state: DependencyState,
+}
+
+impl VaultDependency {
+    /// Whether a vault in `status` satisfies this dependency.
+    pub fn is_met_by(&self, status: VaultStatus) -> bool {
+        match self.required_state {
+            DependencyState::Unlockable => matches!(status, VaultStatus::Unlockable | VaultStatus::Unlocked),
+            DependencyState::Unlocked => status == VaultStatus::Unlocked,
+        }
+    }
 }
 
 /// Represents the counts of approvals received.
@@ -44,15 +336,15 @@ pub struct ApprovalCounts {
     pub witness_approvals: u32,
 }
 
-// Example Recovery QR Config (if needed later)
-// #[derive(Clone, Debug, Default, CandidType, Serialize, Deserialize, PartialEq)]
-// pub struct RecoveryQrConfig {
-//     pub enabled: bool,
-//     /// If true, QR bypasses the threshold check.
-//     pub bypass_threshold: bool,
-//     /// If true, QR is only valid if no heirs/witnesses have joined.
-//     pub valid_if_no_members: bool,
-// }
+/// Recovery QR configuration (see PRD).
+#[derive(Clone, Debug, Default, CandidType, Serialize, Deserialize, PartialEq)]
+pub struct RecoveryQrConfig {
+    pub enabled: bool,
+    /// If true, QR bypasses the threshold check.
+    pub bypass_threshold: bool,
+    /// If true, QR is only valid if no heirs/witnesses have joined.
+    pub valid_if_no_heir_or_witness: bool,
+}
 
 // Implement Default if needed for initialization
 impl Default for VaultConfig {
@@ -72,17 +364,114 @@ impl Default for VaultConfig {
             expires_at: 0, // Needs proper calculation
             unlocked_at: None,
             last_accessed_by_owner: None,
+            activity_source: ActivitySource::default(),
+            renewal_payer: None,
+            pending_owner_transfer: None,
+            owner_recovery: None,
+            co_owners: Vec::new(),
+            pending_co_owners: Vec::new(),
+            require_joint_approval: false,
+            pending_joint_actions: Vec::new(),
+            emergency_contacts: Vec::new(),
+            disclosure_stages: Vec::new(),
+            unlockable_at: None,
+            post_unlock: PostUnlockPolicy::default(),
+            receipt_witness: None,
+            restore_window_days: DEFAULT_RESTORE_WINDOW_DAYS,
+            soft_deleted: None,
+            max_content_versions: DEFAULT_MAX_CONTENT_VERSIONS,
+            legal_hold: None,
+            successor_activation_days: 0,
+            unlock_requested_at: None,
+            challenge_window_days: DEFAULT_CHALLENGE_WINDOW_DAYS,
+            unlock_challenge: None,
             // schema_version: 1,
         }
     }
 }
 
+impl VaultConfig {
+    /// End of the restore window for something deleted at `deleted_at`.
+    pub fn restore_deadline(&self, deleted_at: Timestamp) -> Timestamp {
+        deleted_at.saturating_add(self.restore_window_days as u64 * 24 * 60 * 60 * 1_000_000_000)
+    }
+
+    /// Fails while the vault is under legal hold.
+    pub fn check_legal_hold(&self) -> Result<(), VaultError> {
+        match &self.legal_hold {
+            Some(hold) => Err(VaultError::LegalHold(hold.reason.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails while a challenge against the unlock is open.
+    pub fn check_unlock_challenge(&self) -> Result<(), VaultError> {
+        match &self.unlock_challenge {
+            Some(challenge) => Err(VaultError::UnlockChallenged(challenge.reason.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// End of the window in which the unlock can be challenged, once the vault has become Unlockable.
+    pub fn challenge_deadline(&self) -> Option<Timestamp> {
+        self.unlockable_at
+            .map(|at| at.saturating_add(self.challenge_window_days as u64 * 24 * 60 * 60 * 1_000_000_000))
+    }
+
+    /// True if heirs may read `content_id`: the vault is unlockable and the item is either
+    /// unstaged or in a stage that has been released.
+    pub fn is_content_released(&self, content_id: &ContentId) -> bool {
+        if !matches!(self.status, VaultStatus::Unlockable | VaultStatus::Unlocked) {
+            return false;
+        }
+        self.disclosure_stages
+            .iter()
+            .find(|s| s.spec.content_ids.contains(content_id))
+            .map_or(true, |s| s.status == DisclosureStageStatus::Unlockable)
+    }
+
+    /// True for the primary owner and every co-owner.
+    pub fn is_owner(&self, principal: &PrincipalId) -> bool {
+        self.owner == *principal || self.co_owners.iter().any(|c| c.principal == *principal)
+    }
+
+    /// The primary owner has every permission; co-owners only those granted to them.
+    pub fn has_owner_permission(&self, principal: &PrincipalId, permission: OwnerPermission) -> bool {
+        self.owner == *principal
+            || self.co_owners.iter().any(|c| c.principal == *principal && c.permissions.contains(&permission))
+    }
+
+    /// All owner principals, primary first.
+    pub fn all_owners(&self) -> Vec<PrincipalId> {
+        std::iter::once(self.owner).chain(self.co_owners.iter().map(|c| c.principal)).collect()
+    }
+
+    /// The activity timestamp the inactivity condition counts from, combining co-owners
+    /// according to `inactivity_mode` (latest activity for AllOwners, earliest for AnyOwner).
+    pub fn owner_activity_reference(&self) -> Timestamp {
+        let primary = self.last_accessed_by_owner.unwrap_or(self.created_at);
+        let co_owner_times = self.co_owners.iter().map(|c| c.last_active_at.unwrap_or(c.added_at));
+        let all = std::iter::once(primary).chain(co_owner_times);
+        match self.unlock_conditions.inactivity_mode {
+            InactivityMode::AllOwners => all.max().unwrap_or(primary),
+            InactivityMode::AnyOwner => all.min().unwrap_or(primary),
+        }
+    }
+}
+
 impl PartialEq for UnlockConditions {
     fn eq(&self, other: &Self) -> bool {
         self.time_based_unlock_epoch_sec == other.time_based_unlock_epoch_sec
             && self.inactivity_duration_sec == other.inactivity_duration_sec
             && self.required_heir_approvals == other.required_heir_approvals
             && self.required_witness_approvals == other.required_witness_approvals
+            && self.inactivity_warning_days == other.inactivity_warning_days
+            && self.recovery_qr == other.recovery_qr
+            && self.inactivity_mode == other.inactivity_mode
+            && self.depends_on_vaults == other.depends_on_vaults
+            && self.required_death_attestations == other.required_death_attestations
+            && self.required_heir_approval_weight == other.required_heir_approval_weight
+            && self.required_witness_approval_weight == other.required_witness_approval_weight
     }
     fn ne(&self, other: &Self) -> bool {
         !self.eq(other)
@@ -1,5 +1,5 @@
 // src/backend/models/vault_content_item.rs
-use crate::models::common::{ContentId, ContentType, Timestamp, VaultId};
+use crate::models::common::{ContentId, ContentType, PrincipalId, Timestamp, VaultId};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -20,4 +20,52 @@ pub struct VaultContentItem {
     pub payload: Vec<u8>, // file: file blob, Password and Letter: json string in Vec<u8>
     pub payload_size_bytes: u64,
     pub payload_sha256: Option<String>, // Optional checksum for verification
+
+    // Time capsule: released to `recipients` at `release_at`, independent of the vault unlock
+    #[serde(default)]
+    pub release_at: Option<Timestamp>,
+    #[serde(default)]
+    pub recipients: Vec<PrincipalId>,
+    #[serde(default)]
+    pub released_at: Option<Timestamp>, // Set by the scheduler once `release_at` has passed
+
+    #[serde(default)]
+    pub deleted_at: Option<Timestamp>, // Soft-deleted: hidden and off the quota until restored or purged
+
+    // Version history: the current payload is `version`; earlier payloads live in `content_versions` storage
+    #[serde(default = "first_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub updated_by: Option<PrincipalId>, // Who wrote the current version (None for items predating history)
+    #[serde(default)]
+    pub history: Vec<ContentVersionInfo>, // Retained previous versions, oldest first
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+/// A retained previous version of a content item. Its payload is stored under `(content_id, version)`.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ContentVersionInfo {
+    pub version: u32,
+    pub payload_sha256: Option<String>,
+    pub payload_size_bytes: u64,
+    pub written_at: Timestamp,           // When this version became current
+    pub written_by: Option<PrincipalId>,
+    pub replaced_at: Timestamp,
+}
+
+/// Payload of a retained version, stored apart from the item.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct VersionBlob {
+    pub vault_id: VaultId,
+    pub payload: Vec<u8>,
+}
+
+impl VaultContentItem {
+    /// Bytes held by the item: the current payload plus every retained version.
+    pub fn retained_bytes(&self) -> u64 {
+        self.history.iter().fold(self.payload_size_bytes, |sum, v| sum.saturating_add(v.payload_size_bytes))
+    }
 }
\ No newline at end of file
@@ -45,6 +45,8 @@ pub struct VaultInviteToken {
     pub claimed_by: Option<Principal>,
     pub shamir_share_index: u8,
     pub share_data: Vec<u8>,    // Serialized Shamir share data
+    #[serde(default)]
+    pub successor_of: Option<Principal>, // Successor invites: the heir they back up; claimed as a dormant member
 }
 
 // Implement Default for easier initialization if needed
@@ -65,6 +67,7 @@ impl Default for VaultInviteToken {
             claimed_by: None,
             shamir_share_index: 0, // Default to 0, must be assigned properly
             share_data: Vec::new(), // Default to empty vec
+            successor_of: None,
         }
     }
 }
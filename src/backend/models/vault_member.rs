@@ -6,9 +6,11 @@ use serde::{Deserialize, Serialize};
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
 pub struct AccessControl {
     pub last_accessed_at: Option<Timestamp>,
-    pub download_limit_per_day: u8, // e.g., 3 as per PRD
+    pub download_limit_per_day: u8, // e.g., 3 as per PRD; 0 means unlimited
     pub daily_downloads_count: u8,
     pub last_download_day_index: u64, // To track daily reset
+    #[serde(default)]
+    pub custom_limit: bool, // Set by the owner for this member; vault-wide changes leave it alone
 }
 
 #[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
@@ -28,6 +30,30 @@ pub struct VaultMember {
     pub updated_at: Timestamp,
     pub access_control: AccessControl, // Manages access after unlock
     pub has_approved_unlock: bool, // Track approval status
+    #[serde(default)]
+    pub successor_of: Option<PrincipalId>, // For successors: the heir whose place they take on activation
+    #[serde(default = "default_approval_weight")]
+    pub approval_weight: u32, // How much this member's unlock approval counts toward weighted thresholds
+}
+
+pub const DEFAULT_APPROVAL_WEIGHT: u32 = 1;
+
+fn default_approval_weight() -> u32 {
+    DEFAULT_APPROVAL_WEIGHT
+}
+
+impl VaultMember {
+    /// Whether the member takes part in the vault (approvals, notifications, access).
+    /// Revoked members and dormant successors do not.
+    pub fn is_participating(&self) -> bool {
+        !matches!(self.status, MemberStatus::Revoked | MemberStatus::Dormant)
+    }
+
+    /// Whether the member may be given access to content (released stages, time capsules,
+    /// emergency grants). Managers administer the vault but never see its content.
+    pub fn may_receive_content(&self) -> bool {
+        self.is_participating() && self.role != Role::Manager
+    }
 }
 
 #[derive(Clone, Debug, candid::CandidType, serde::Deserialize, serde::Serialize)]
@@ -63,8 +89,11 @@ impl Default for VaultMember {
                 download_limit_per_day: 3,
                 daily_downloads_count: 0,
                 last_download_day_index: 0,
+                custom_limit: false,
             },
             has_approved_unlock: false,
+            successor_of: None,
+            approval_weight: DEFAULT_APPROVAL_WEIGHT,
         }
     }
 }
@@ -76,6 +105,7 @@ impl Default for AccessControl {
             download_limit_per_day: 3,
             daily_downloads_count: 0,
             last_download_day_index: 0,
+            custom_limit: false,
         }
     }
 }
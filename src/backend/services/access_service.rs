@@ -0,0 +1,420 @@
+// src/backend/services/access_service.rs
+// Content read access: owners, heirs after unlock, time-capsule recipients, and emergency
+// access grants (a named member gets scoped items after a waiting period unless an owner rejects).
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        emergency_access::{EmergencyAccessGrant, EmergencyAccessStatus},
+        notification::{NotificationKind, NotificationRecipient},
+        vault_config::{EmergencyContact, OwnerPermission, PostUnlockPolicy, VaultConfig},
+        vault_content_item::VaultContentItem,
+        vault_member::VaultMember,
+    },
+    services::{notification_service, receipt_service},
+    storage,
+};
+use candid::CandidType;
+use ic_cdk::api::time;
+use serde::{Deserialize, Serialize};
+
+const DAY_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const MAX_WAIT_DAYS: u32 = 90;
+const MAX_CONTENT_IDS_PER_GRANT: usize = 100;
+
+/// A member's remaining download allowance.
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct DownloadQuota {
+    pub daily_limit: u8,             // 0 means unlimited
+    pub remaining_today: Option<u8>, // None when unlimited
+    pub access_until: Option<u64>,   // Epoch seconds
+}
+
+/// A downloaded item with the caller's remaining quota (None for owners, and before the unlock).
+#[derive(CandidType, Deserialize, Serialize, Clone, Debug)]
+pub struct ContentDownload {
+    pub item: VaultContentItem,
+    pub quota: Option<DownloadQuota>,
+}
+
+fn notify_grantee(grant: &EmergencyAccessGrant, kind: NotificationKind, key: String) {
+    if let Err(e) = notification_service::enqueue(&grant.vault_id, kind, NotificationRecipient::Principal(grant.grantee), key) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to enqueue emergency access notification for vault {}: {:?}", grant.vault_id, e);
+    }
+}
+
+/// True if `principal` is a member of the vault who may be given content access (not revoked, dormant or a manager).
+fn is_active_member(vault_id: &VaultId, principal: &PrincipalId) -> bool {
+    storage::members::get_member(vault_id, principal).map_or(false, |m| m.may_receive_content())
+}
+
+/// Loads a content item and checks that it belongs to the vault.
+fn get_vault_content(vault_id: &VaultId, content_id: &ContentId) -> Result<VaultContentItem, VaultError> {
+    storage::content::get_internal_content_id(*content_id)
+        .and_then(storage::content::get_content)
+        .filter(|item| item.vault_id == *vault_id && item.deleted_at.is_none())
+        .ok_or(VaultError::ContentNotFound(*content_id))
+}
+
+/// Adds, updates (`wait_days: Some`) or removes (`None`) an emergency contact.
+/// Removing a contact revokes their open grants.
+pub fn set_emergency_contact(
+    vault_id: &VaultId,
+    member: PrincipalId,
+    wait_days: Option<u32>,
+    caller: PrincipalId,
+) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageMembers) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can manage emergency contacts".to_string()));
+    }
+
+    config.emergency_contacts.retain(|c| c.member != member);
+    match wait_days {
+        Some(days) => {
+            if days == 0 || days > MAX_WAIT_DAYS {
+                return Err(VaultError::InvalidInput(format!("Waiting period must be between 1 and {} days", MAX_WAIT_DAYS)));
+            }
+            if !is_active_member(vault_id, &member) {
+                return Err(VaultError::MemberNotFound(member.to_string()));
+            }
+            config.emergency_contacts.push(EmergencyContact { member, wait_days: days });
+        }
+        None => {
+            let current_time = time();
+            for mut grant in storage::emergency_access::get_grants(vault_id) {
+                if grant.grantee == member && matches!(grant.status, EmergencyAccessStatus::Pending | EmergencyAccessStatus::Active) {
+                    grant.status = EmergencyAccessStatus::Revoked;
+                    grant.resolved_at = Some(current_time);
+                    storage::emergency_access::upsert_grant(grant);
+                }
+            }
+        }
+    }
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::EmergencyContactChanged, caller, format!("Emergency contact {} set to {:?} day(s)", member, wait_days));
+    Ok(())
+}
+
+/// Requests emergency access to specific content items. Emergency contacts only.
+/// The grant becomes active after the contact's waiting period unless an owner rejects it.
+pub fn request_emergency_access(
+    vault_id: &VaultId,
+    content_ids: Vec<ContentId>,
+    caller: PrincipalId,
+) -> Result<EmergencyAccessGrant, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let contact = config
+        .emergency_contacts
+        .iter()
+        .find(|c| c.member == caller)
+        .ok_or_else(|| VaultError::NotAuthorized("Caller is not an emergency contact of this vault".to_string()))?;
+    if !is_active_member(vault_id, &caller) {
+        return Err(VaultError::NotAuthorized("Caller's membership has been revoked".to_string()));
+    }
+    if matches!(config.status, VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Vault {} is {:?}", vault_id, config.status)));
+    }
+
+    let mut content_ids = content_ids;
+    content_ids.sort();
+    content_ids.dedup();
+    if content_ids.is_empty() || content_ids.len() > MAX_CONTENT_IDS_PER_GRANT {
+        return Err(VaultError::InvalidInput(format!("Request between 1 and {} content items", MAX_CONTENT_IDS_PER_GRANT)));
+    }
+    for content_id in &content_ids {
+        get_vault_content(vault_id, content_id)?;
+    }
+
+    let grants = storage::emergency_access::get_grants(vault_id);
+    if grants.iter().any(|g| g.grantee == caller && g.status == EmergencyAccessStatus::Pending) {
+        return Err(VaultError::InvalidState("An emergency access request is already pending".to_string()));
+    }
+
+    let current_time = time();
+    // Request timestamp is the id; bump it if two requests land in the same instant
+    let mut grant_id = current_time;
+    while grants.iter().any(|g| g.grant_id == grant_id) {
+        grant_id += 1;
+    }
+    let grant = EmergencyAccessGrant {
+        grant_id,
+        vault_id: *vault_id,
+        grantee: caller,
+        content_ids,
+        status: EmergencyAccessStatus::Pending,
+        requested_at: current_time,
+        available_at: current_time + contact.wait_days as u64 * DAY_NANOS,
+        resolved_at: None,
+    };
+    storage::emergency_access::upsert_grant(grant.clone());
+
+    storage::audit_logs::record(vault_id, LogAction::EmergencyAccessRequested, caller, format!(
+        "Emergency access {} requested for {} item(s), available at {}",
+        grant_id, grant.content_ids.len(), grant.available_at
+    ));
+    notification_service::notify_owner(
+        vault_id,
+        NotificationKind::EmergencyAccessRequested { grant_id, grantee: caller, available_at: grant.available_at },
+        format!("emergency_access_requested:{}:{}", vault_id, grant_id),
+    );
+    Ok(grant)
+}
+
+fn find_grant(vault_id: &VaultId, grant_id: u64) -> Result<EmergencyAccessGrant, VaultError> {
+    storage::emergency_access::get_grants(vault_id)
+        .into_iter()
+        .find(|g| g.grant_id == grant_id)
+        .ok_or_else(|| VaultError::NotFound(format!("Emergency access grant {} not found", grant_id)))
+}
+
+/// Rejects a pending request during its waiting period. Owners only.
+pub fn reject_emergency_access(vault_id: &VaultId, grant_id: u64, caller: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can reject emergency access".to_string()));
+    }
+    let mut grant = find_grant(vault_id, grant_id)?;
+    if grant.status != EmergencyAccessStatus::Pending {
+        return Err(VaultError::InvalidState(format!("Emergency access grant is {:?}", grant.status)));
+    }
+
+    grant.status = EmergencyAccessStatus::Rejected;
+    grant.resolved_at = Some(time());
+    storage::emergency_access::upsert_grant(grant.clone());
+
+    storage::audit_logs::record(vault_id, LogAction::EmergencyAccessRejected, caller, format!("Emergency access {} rejected", grant_id));
+    notify_grantee(&grant, NotificationKind::EmergencyAccessRejected { grant_id }, format!("emergency_access_rejected:{}:{}", vault_id, grant_id));
+    Ok(())
+}
+
+/// Ends an active grant. Owners, or the grantee giving it up.
+pub fn revoke_emergency_access(vault_id: &VaultId, grant_id: u64, caller: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let mut grant = find_grant(vault_id, grant_id)?;
+    if !config.is_owner(&caller) && grant.grantee != caller {
+        return Err(VaultError::NotAuthorized("Only the vault owner or the grantee can revoke emergency access".to_string()));
+    }
+    if grant.status != EmergencyAccessStatus::Active {
+        return Err(VaultError::InvalidState(format!("Emergency access grant is {:?}", grant.status)));
+    }
+
+    grant.status = EmergencyAccessStatus::Revoked;
+    grant.resolved_at = Some(time());
+    storage::emergency_access::upsert_grant(grant);
+
+    storage::audit_logs::record(vault_id, LogAction::EmergencyAccessRevoked, caller, format!("Emergency access {} revoked", grant_id));
+    Ok(())
+}
+
+/// Lists emergency access grants (newest first): all of them for owners, the caller's own otherwise.
+pub fn get_emergency_access_grants(vault_id: &VaultId, caller: PrincipalId) -> Result<Vec<EmergencyAccessGrant>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let is_owner = config.is_owner(&caller);
+    if !is_owner && !storage::members::is_member(vault_id, &caller) {
+        return Err(VaultError::NotAuthorized("Caller is not a member of this vault".to_string()));
+    }
+
+    let mut grants: Vec<EmergencyAccessGrant> = storage::emergency_access::get_grants(vault_id)
+        .into_iter()
+        .filter(|g| is_owner || g.grantee == caller)
+        .collect();
+    grants.reverse();
+    Ok(grants)
+}
+
+/// Activates pending grants whose waiting period has ended.
+/// Called by the scheduler.
+pub fn process_emergency_access(current_time: Timestamp) -> Result<(), VaultError> {
+    let due = storage::emergency_access::get_due_pending_grants(current_time);
+    let mut activated = 0u32;
+
+    for mut grant in due {
+        let vault_id = grant.vault_id;
+        if storage::vault_configs::is_on_legal_hold(&vault_id) {
+            continue; // Stays pending until the hold is released
+        }
+        let still_contact = storage::vault_configs::get_vault_config(&vault_id)
+            .map_or(false, |c| c.emergency_contacts.iter().any(|e| e.member == grant.grantee));
+        if !still_contact || !is_active_member(&vault_id, &grant.grantee) {
+            grant.status = EmergencyAccessStatus::Revoked;
+            grant.resolved_at = Some(current_time);
+            storage::emergency_access::upsert_grant(grant);
+            continue;
+        }
+
+        grant.status = EmergencyAccessStatus::Active;
+        grant.resolved_at = Some(current_time);
+        storage::emergency_access::upsert_grant(grant.clone());
+        activated += 1;
+
+        storage::audit_logs::record(&vault_id, LogAction::EmergencyAccessActivated, ic_cdk::id(), format!(
+            "Emergency access {} for {} activated after waiting period", grant.grant_id, grant.grantee
+        ));
+        notify_grantee(
+            &grant,
+            NotificationKind::EmergencyAccessGranted { grant_id: grant.grant_id },
+            format!("emergency_access_granted:{}:{}", vault_id, grant.grant_id),
+        );
+    }
+
+    if activated > 0 {
+        ic_cdk::print(format!("🚨 INFO: Activated {} emergency access grant(s).", activated));
+    }
+    Ok(())
+}
+
+/// Checks whether `caller` may read `item`: owners always, heirs once the item's disclosure
+/// stage is released, recipients of a released time capsule, and members holding an active
+/// emergency grant covering the item.
+pub fn can_read_content(config: &VaultConfig, item: &VaultContentItem, caller: &PrincipalId) -> bool {
+    if item.deleted_at.is_some() {
+        return false; // In the trash: restore it first
+    }
+    if config.is_owner(caller) {
+        return true;
+    }
+    let member = match storage::members::get_member(&config.vault_id, caller) {
+        Some(m) if m.may_receive_content() => m,
+        _ => return false,
+    };
+    if member.role == Role::Heir && config.is_content_released(&item.content_id) {
+        return true;
+    }
+    // Time capsules and emergency grants apply regardless of the vault status
+    if item.released_at.is_some() && item.recipients.contains(caller) {
+        return true;
+    }
+    storage::emergency_access::get_grants(&config.vault_id)
+        .iter()
+        .any(|g| g.covers(caller, &item.content_id))
+}
+
+/// Returns a content item if the caller may read it. Member downloads of an unlockable or unlocked
+/// vault go through the post-unlock policy (`access_until`, then the member's daily quota).
+pub fn download_content(vault_id: &VaultId, content_id: &ContentId, caller: PrincipalId) -> Result<ContentDownload, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?; // No downloads at all while held, the owner's included
+    let item = get_vault_content(vault_id, content_id)?;
+    if !can_read_content(&config, &item, &caller) {
+        return Err(VaultError::NotAuthorized(format!("Caller cannot read content {}", content_id)));
+    }
+    if config.is_owner(&caller) {
+        return Ok(ContentDownload { item, quota: None });
+    }
+    config.check_unlock_challenge()?; // Member downloads wait for the challenge to be resolved
+
+    let member = storage::members::get_member(vault_id, &caller)
+        .ok_or_else(|| VaultError::MemberNotFound(caller.to_string()))?;
+    let current_time = time();
+    // Heirs read released stages while the vault is Unlockable, so the policy applies from there.
+    // Emergency grants and released time capsules are read before the unlock, outside the policy
+    let quota = if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked) {
+        Some(consume_download(&config, member, current_time)?)
+    } else {
+        None
+    };
+    receipt_service::record_download(vault_id, &caller, content_id, current_time);
+    storage::audit_logs::record(vault_id, LogAction::ContentDownloaded, caller, format!("Content {} downloaded", content_id));
+    Ok(ContentDownload { item, quota })
+}
+
+// --- Post-unlock policy engine ---
+
+fn day_index(timestamp: Timestamp) -> u64 {
+    timestamp / DAY_NANOS
+}
+
+/// Computes a member's quota at `current_time`, treating a counter from an earlier day as reset.
+fn quota_for(config: &VaultConfig, member: &VaultMember, current_time: Timestamp) -> DownloadQuota {
+    let acl = &member.access_control;
+    let used_today = if acl.last_download_day_index == day_index(current_time) { acl.daily_downloads_count } else { 0 };
+    DownloadQuota {
+        daily_limit: acl.download_limit_per_day,
+        remaining_today: (acl.download_limit_per_day > 0).then(|| acl.download_limit_per_day.saturating_sub(used_today)),
+        access_until: config.post_unlock.access_until,
+    }
+}
+
+/// Checks the access period and daily limit, then counts one download for the member.
+fn consume_download(config: &VaultConfig, mut member: VaultMember, current_time: Timestamp) -> Result<DownloadQuota, VaultError> {
+    if config.post_unlock.access_ended(current_time) {
+        return Err(VaultError::AccessPeriodEnded(format!(
+            "Access to vault {} ended at {} (epoch seconds)", config.vault_id, config.post_unlock.access_until.unwrap_or_default()
+        )));
+    }
+    let quota = quota_for(config, &member, current_time);
+    if quota.remaining_today == Some(0) {
+        return Err(VaultError::DownloadLimitReached(format!("{} download(s) per day", quota.daily_limit)));
+    }
+
+    let today = day_index(current_time);
+    let acl = &mut member.access_control;
+    if acl.last_download_day_index != today {
+        acl.last_download_day_index = today;
+        acl.daily_downloads_count = 0;
+    }
+    acl.daily_downloads_count = acl.daily_downloads_count.saturating_add(1);
+    acl.last_accessed_at = Some(current_time);
+    member.updated_at = current_time;
+    storage::members::insert_member(&member);
+
+    Ok(quota_for(config, &member, current_time))
+}
+
+/// Returns the caller's download quota for a vault. Members only.
+pub fn get_download_quota(vault_id: &VaultId, caller: PrincipalId) -> Result<DownloadQuota, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let member = storage::members::get_member(vault_id, &caller)
+        .ok_or_else(|| VaultError::MemberNotFound(caller.to_string()))?;
+    Ok(quota_for(&config, &member, time()))
+}
+
+/// Sets the vault's post-unlock policy. Members without a custom limit follow the new default.
+pub fn set_post_unlock_policy(vault_id: &VaultId, policy: PostUnlockPolicy, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can change the access policy".to_string()));
+    }
+    let current_time = time();
+    if policy.access_until_nanos().map_or(false, |t| t <= current_time) {
+        return Err(VaultError::InvalidInput("access_until must be in the future".to_string()));
+    }
+
+    if policy.daily_download_limit != config.post_unlock.daily_download_limit {
+        for mut member in storage::members::get_members_by_vault(vault_id) {
+            if !member.access_control.custom_limit {
+                member.access_control.download_limit_per_day = policy.daily_download_limit;
+                storage::members::insert_member(&member);
+            }
+        }
+    }
+    config.post_unlock = policy;
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::AccessPolicyChanged, caller, format!("Post-unlock policy set to {:?}", config.post_unlock));
+    Ok(())
+}
+
+/// Overrides one member's daily download limit (`Some`), or returns them to the vault default (`None`).
+pub fn set_member_download_limit(vault_id: &VaultId, member: PrincipalId, limit: Option<u8>, caller: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageMembers) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can change member limits".to_string()));
+    }
+    let mut record = storage::members::get_member(vault_id, &member)
+        .ok_or_else(|| VaultError::MemberNotFound(member.to_string()))?;
+
+    record.access_control.custom_limit = limit.is_some();
+    record.access_control.download_limit_per_day = limit.unwrap_or(config.post_unlock.daily_download_limit);
+    record.updated_at = time();
+    storage::members::insert_member(&record);
+
+    storage::audit_logs::record(vault_id, LogAction::AccessPolicyChanged, caller, format!("Download limit for {} set to {:?}", member, limit));
+    Ok(())
+}
@@ -0,0 +1,106 @@
+// src/backend/services/activity_service.rs
+// Owner proof-of-life tracking for the inactivity unlock condition.
+
+use crate::{
+    error::VaultError,
+    models::{
+        activity::{ActivityKind, ActivityRecord},
+        common::*,
+        inactivity_warning::InactivityWarning,
+    },
+    storage,
+};
+use ic_cdk::api::time;
+
+/// Records an owner action against a vault.
+/// The action only resets `last_accessed_by_owner` (and is kept in the activity history)
+/// if the vault's `activity_source` setting counts this kind of action.
+///
+/// # Returns
+/// * `Result<bool, VaultError>` - True if the action was counted as activity.
+pub fn record_owner_activity(vault_id: &VaultId, actor: PrincipalId, kind: ActivityKind) -> Result<bool, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+
+    // Only owners' actions count as proof of life
+    if !config.is_owner(&actor) {
+        return Ok(false);
+    }
+
+    if !config.activity_source.counts(kind) {
+        ic_cdk::print(format!(
+            "ℹ️ INFO: {:?} on vault {} not counted as activity (source: {:?}).",
+            kind, vault_id, config.activity_source
+        ));
+        return Ok(false);
+    }
+
+    let current_time = time();
+    if config.owner == actor {
+        config.last_accessed_by_owner = Some(current_time);
+    } else if let Some(co_owner) = config.co_owners.iter_mut().find(|c| c.principal == actor) {
+        co_owner.last_active_at = Some(current_time);
+    }
+    // A living owner answers any outstanding unlock request, so successor activation waits for the next one
+    config.unlock_requested_at = None;
+    storage::vault_configs::insert_vault_config(&config);
+    storage::activity::add_activity(vault_id, ActivityRecord { timestamp: current_time, kind });
+
+    Ok(true)
+}
+
+/// Counts an owner's successful update call on a vault as activity. Called by the update endpoints
+/// once the service call succeeded; errors are logged, never returned, so bookkeeping cannot fail
+/// an update that already happened. Calls by non-owners are ignored by `record_owner_activity`.
+pub fn note_owner_update(vault_id: &VaultId, actor: PrincipalId, kind: ActivityKind) {
+    if let Err(e) = record_owner_activity(vault_id, actor, kind) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to record owner activity on vault {}: {:?}", vault_id, e);
+    }
+}
+
+/// Explicit proof-of-life: touches every vault owned (or co-owned) by the caller.
+///
+/// # Returns
+/// * `Result<u32, VaultError>` - The number of vaults whose inactivity timer was reset.
+pub fn heartbeat(owner: PrincipalId) -> Result<u32, VaultError> {
+    let owned = storage::get_vaults_config_by_owner(owner);
+    let mut touched = 0u32;
+
+    for config in owned {
+        // Vaults that are already past the point of no return are not touched
+        if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+            continue;
+        }
+        if record_owner_activity(&config.vault_id, owner, ActivityKind::Heartbeat)? {
+            touched += 1;
+        }
+    }
+
+    ic_cdk::print(format!("💓 INFO: Heartbeat from {} reset {} vault(s).", owner, touched));
+    Ok(touched)
+}
+
+/// Returns the recent owner activity history for a vault (newest first).
+/// Only the owner may view it.
+pub fn get_activity_history(vault_id: &VaultId, caller: PrincipalId) -> Result<Vec<ActivityRecord>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can view activity history".to_string()));
+    }
+
+    let mut records = storage::activity::get_activity(vault_id);
+    records.reverse();
+    Ok(records)
+}
+
+/// Returns the inactivity warnings issued for a vault (newest first).
+/// Only the owner may view them.
+pub fn get_inactivity_warnings(vault_id: &VaultId, caller: PrincipalId) -> Result<Vec<InactivityWarning>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can view inactivity warnings".to_string()));
+    }
+
+    let mut warnings = storage::inactivity_warnings::get_warnings(vault_id);
+    warnings.reverse();
+    Ok(warnings)
+}
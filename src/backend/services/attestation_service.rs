@@ -0,0 +1,113 @@
+// src/backend/services/attestation_service.rs
+// Authority-attested events: an admin-managed registry of trusted authorities (notaries,
+// registries, institutions) whose death attestations count toward a vault's unlock conditions.
+
+use crate::{
+    error::VaultError,
+    models::{
+        attestation::{AttestationKind, EventAttestation, TrustedAuthority},
+        audit_log::LogAction,
+        common::*,
+        notification::NotificationKind,
+    },
+    services::{notification_service, vault_service},
+    storage,
+};
+use ic_cdk::api::time;
+
+const MAX_AUTHORITY_NAME_LEN: usize = 100;
+
+fn ensure_admin(caller: PrincipalId) -> Result<(), VaultError> {
+    if storage::config::get_admin_principal() != caller {
+        return Err(VaultError::AdminGuardFailed);
+    }
+    Ok(())
+}
+
+/// Adds (or renames) a trusted authority. Admin only.
+pub fn add_trusted_authority(principal: PrincipalId, name: String, caller: PrincipalId) -> Result<TrustedAuthority, VaultError> {
+    ensure_admin(caller)?;
+    let name = name.trim().to_string();
+    if name.is_empty() || name.len() > MAX_AUTHORITY_NAME_LEN {
+        return Err(VaultError::InvalidInput(format!("Authority name must be 1 to {} characters", MAX_AUTHORITY_NAME_LEN)));
+    }
+    let authority = TrustedAuthority { principal, name, added_by: caller, added_at: time() };
+    storage::attestations::insert_authority(authority.clone());
+    ic_cdk::print(format!("🏛️ INFO: Trusted authority {} ({}) added by {}", principal, authority.name, caller));
+    Ok(authority)
+}
+
+/// Removes a trusted authority. Its past attestations stop counting toward unlock. Admin only.
+pub fn remove_trusted_authority(principal: PrincipalId, caller: PrincipalId) -> Result<(), VaultError> {
+    ensure_admin(caller)?;
+    storage::attestations::remove_authority(&principal)
+        .ok_or_else(|| VaultError::NotFound(format!("Trusted authority {}", principal)))?;
+    ic_cdk::print(format!("🏛️ INFO: Trusted authority {} removed by {}", principal, caller));
+    Ok(())
+}
+
+/// Lists the trusted authorities. Admin only.
+pub fn list_trusted_authorities(caller: PrincipalId) -> Result<Vec<TrustedAuthority>, VaultError> {
+    ensure_admin(caller)?;
+    Ok(storage::attestations::get_authorities())
+}
+
+/// Number of distinct, still trusted authorities that attested `kind` for the vault.
+pub(crate) fn count_trusted_attestations(vault_id: &VaultId, kind: AttestationKind) -> u32 {
+    storage::attestations::get_vault_attestations(vault_id)
+        .iter()
+        .filter(|a| a.kind == kind && storage::attestations::is_trusted_authority(&a.authority))
+        .count() as u32
+}
+
+/// Records a trusted authority's attestation for a vault. The evidence hash goes into the audit log,
+/// the owner is notified, and the vault is made Unlockable if its conditions are now met.
+pub async fn attest_event(vault_id: &VaultId, kind: AttestationKind, evidence_hash: String, caller: PrincipalId) -> Result<EventAttestation, VaultError> {
+    if !storage::attestations::is_trusted_authority(&caller) {
+        return Err(VaultError::NotAuthorized("Only trusted authorities can submit attestations".to_string()));
+    }
+    let evidence_hash = evidence_hash.trim().to_lowercase();
+    if evidence_hash.len() != 64 || !evidence_hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(VaultError::InvalidInput("Evidence hash must be a hex SHA-256 digest".to_string()));
+    }
+    let config = storage::vault_configs::require(vault_id)?;
+    if matches!(config.status, VaultStatus::Draft | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Vault cannot be attested in status {:?}", config.status)));
+    }
+
+    let attestation = EventAttestation {
+        vault_id: *vault_id,
+        authority: caller,
+        kind,
+        evidence_hash: evidence_hash.clone(),
+        attested_at: time(),
+    };
+    if !storage::attestations::add_attestation(attestation.clone()) {
+        return Err(VaultError::InvalidState(format!("{:?} was already attested by this authority", kind)));
+    }
+    storage::audit_logs::record(vault_id, LogAction::EventAttested, caller, format!("{:?} attested, evidence sha256 {}", kind, evidence_hash));
+
+    // A living owner learns of the attestation and can dispute it
+    notification_service::notify_owner(
+        vault_id,
+        NotificationKind::EventAttested { authority: caller, kind },
+        format!("event_attested:{}:{}:{:?}", vault_id, caller, kind),
+    );
+
+    if matches!(config.status, VaultStatus::Active | VaultStatus::GraceHeir) && config.legal_hold.is_none() {
+        if vault_service::check_unlock_conditions(&config).await? {
+            vault_service::set_vault_status(vault_id, VaultStatus::Unlockable, Some(caller)).await?;
+        }
+    }
+    Ok(attestation)
+}
+
+/// Lists a vault's attestations. Owners, members and admin.
+pub fn get_attestations(vault_id: &VaultId, caller: PrincipalId) -> Result<Vec<EventAttestation>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let is_admin = storage::config::get_admin_principal() == caller;
+    if !config.is_owner(&caller) && !storage::members::is_member(vault_id, &caller) && !is_admin {
+        return Err(VaultError::NotAuthorized("Caller is not a member of this vault".to_string()));
+    }
+    Ok(storage::attestations::get_vault_attestations(vault_id))
+}
@@ -0,0 +1,115 @@
+// src/backend/services/challenge_service.rs
+// Post-unlock challenges: within a window after the vault becomes Unlockable, the owner or any heir
+// or witness can object to the unlock. Member downloads pause until an admin clears the challenge or
+// a witness who had not approved the unlock confirms it.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        notification::NotificationKind,
+        vault_config::{UnlockChallenge, VaultConfig},
+    },
+    services::notification_service,
+    storage,
+};
+use ic_cdk::api::time;
+
+const MAX_CHALLENGE_REASON_LEN: usize = 500;
+
+/// Tells the owner, every participating member and the admin about a challenge event.
+fn notify_all(vault_id: &VaultId, kind: NotificationKind, dedupe_key: String) {
+    notification_service::notify_owner(vault_id, kind.clone(), dedupe_key.clone());
+    notification_service::notify_members(vault_id, &[Role::Heir, Role::Witness], kind.clone(), &dedupe_key);
+    let admin = storage::config::get_admin_principal();
+    notification_service::notify_principal(vault_id, admin, kind, format!("{}:{}", dedupe_key, admin));
+}
+
+/// Challenges the unlock of a vault. Owners and participating heirs and witnesses, within the vault's
+/// challenge window. Member downloads are paused until the challenge is resolved.
+pub fn challenge_unlock(vault_id: &VaultId, reason: String, caller: PrincipalId) -> Result<UnlockChallenge, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    // Managers have no say in unlocks
+    let is_member = storage::members::get_member(vault_id, &caller)
+        .map_or(false, |m| m.is_participating() && matches!(m.role, Role::Heir | Role::Witness));
+    if !config.is_owner(&caller) && !is_member {
+        return Err(VaultError::NotAuthorized("Only the owner, heirs and witnesses can challenge an unlock".to_string()));
+    }
+    let reason = reason.trim().to_string();
+    if reason.is_empty() || reason.len() > MAX_CHALLENGE_REASON_LEN {
+        return Err(VaultError::InvalidInput(format!("A reason of 1 to {} characters is required", MAX_CHALLENGE_REASON_LEN)));
+    }
+    if !matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked) {
+        return Err(VaultError::InvalidState(format!("Only an unlocked vault can be challenged (status {:?})", config.status)));
+    }
+    let current_time = time();
+    let within_window = config.challenge_window_days > 0
+        && config.challenge_deadline().map_or(false, |deadline| current_time <= deadline);
+    if !within_window {
+        return Err(VaultError::InvalidState("The challenge window for this unlock has closed".to_string()));
+    }
+    if config.unlock_challenge.is_some() {
+        return Err(VaultError::InvalidState("The unlock is already being challenged".to_string()));
+    }
+
+    let challenge = UnlockChallenge { raised_by: caller, reason: reason.clone(), raised_at: current_time };
+    config.unlock_challenge = Some(challenge.clone());
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+    ic_cdk::print(format!("🚩 INFO: Unlock of vault {} challenged by {}", vault_id, caller));
+    storage::audit_logs::record(vault_id, LogAction::UnlockChallenged, caller, format!("Unlock challenged: {}", reason));
+
+    notify_all(
+        vault_id,
+        NotificationKind::UnlockChallenged { raised_by: caller, reason },
+        format!("unlock_challenged:{}:{}", vault_id, current_time),
+    );
+    Ok(challenge)
+}
+
+/// Closes the open challenge and resumes member downloads.
+fn resolve(mut config: VaultConfig, caller: PrincipalId, details: String) -> Result<(), VaultError> {
+    let challenge = config.unlock_challenge.take()
+        .ok_or_else(|| VaultError::InvalidState("The unlock is not being challenged".to_string()))?;
+    let vault_id = config.vault_id;
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+    ic_cdk::print(format!("🏳️ INFO: Unlock challenge on vault {} resolved by {}", vault_id, caller));
+    storage::audit_logs::record(&vault_id, LogAction::UnlockChallengeResolved, caller, details);
+
+    notify_all(
+        &vault_id,
+        NotificationKind::UnlockChallengeResolved { resolved_by: caller },
+        format!("unlock_challenge_resolved:{}:{}", vault_id, challenge.raised_at),
+    );
+    Ok(())
+}
+
+/// Clears the open challenge after review. Admin only; the note is kept in the audit log.
+pub fn clear_unlock_challenge(vault_id: &VaultId, note: String, caller: PrincipalId) -> Result<(), VaultError> {
+    if storage::config::get_admin_principal() != caller {
+        return Err(VaultError::AdminGuardFailed);
+    }
+    let note = note.trim().to_string();
+    if note.is_empty() || note.len() > MAX_CHALLENGE_REASON_LEN {
+        return Err(VaultError::InvalidInput(format!("A note of 1 to {} characters is required", MAX_CHALLENGE_REASON_LEN)));
+    }
+    let config = storage::vault_configs::require(vault_id)?;
+    resolve(config, caller, format!("Challenge cleared by admin: {}", note))
+}
+
+/// Confirms the unlock despite the open challenge. Only a witness whose approval was not
+/// already counted toward the unlock, and who did not raise the challenge, can confirm it.
+pub fn confirm_challenged_unlock(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let witness = storage::members::get_member(vault_id, &caller)
+        .filter(|m| m.role == Role::Witness && m.is_participating())
+        .ok_or_else(|| VaultError::NotAuthorized("Only a witness can confirm a challenged unlock".to_string()))?;
+    let challenge = config.unlock_challenge.as_ref()
+        .ok_or_else(|| VaultError::InvalidState("The unlock is not being challenged".to_string()))?;
+    if witness.has_approved_unlock || challenge.raised_by == caller {
+        return Err(VaultError::NotAuthorized("The confirming witness must not have approved the unlock or raised the challenge".to_string()));
+    }
+    resolve(config, caller, format!("Challenge resolved by additional witness approval from {}", caller))
+}
@@ -0,0 +1,404 @@
+// src/backend/services/co_owner_service.rs
+// Co-owned (joint) vaults: co-owner management and actions that need every owner's sign-off.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        notification::NotificationKind,
+        payment::PaymentSession,
+        vault_config::{CoOwner, CoOwnerProposal, JointAction, JointActionRequest, OwnerPermission, VaultConfig},
+    },
+    services::{disclosure_service, notification_service, vault_service::{self, VaultUpdateData}},
+    storage,
+};
+use ic_cdk::api::time;
+
+const JOINT_ACTION_EXPIRY_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days to collect approvals
+const MAX_CO_OWNERS: usize = 4;
+const CO_OWNER_PROPOSAL_EXPIRY_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days to accept
+const MAX_PENDING_JOINT_ACTIONS: usize = 10;
+
+/// Drops joint action requests past their expiry. Returns true if any were removed.
+fn prune_expired_joint_actions(config: &mut VaultConfig, current_time: Timestamp) -> bool {
+    let before = config.pending_joint_actions.len();
+    config.pending_joint_actions.retain(|a| a.expires_at > current_time);
+    config.pending_joint_actions.len() != before
+}
+
+/// Offers co-ownership of the vault to `principal`, replacing an earlier offer to them.
+/// Primary owner only. Nothing changes until the invitee accepts.
+pub fn propose_co_owner(
+    vault_id: &VaultId,
+    principal: PrincipalId,
+    permissions: Vec<OwnerPermission>,
+    caller: PrincipalId,
+) -> Result<CoOwnerProposal, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if config.owner != caller {
+        return Err(VaultError::NotAuthorized("Only the primary owner can add co-owners".to_string()));
+    }
+    if principal == PrincipalId::anonymous() {
+        return Err(VaultError::InvalidInput("Invalid co-owner".to_string()));
+    }
+    if config.is_owner(&principal) {
+        return Err(VaultError::InvalidInput(format!("{} already owns vault {}", principal, vault_id)));
+    }
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Cannot add co-owners to a vault in status {:?}", config.status)));
+    }
+    let current_time = time();
+    config.pending_co_owners.retain(|p| p.principal != principal && p.expires_at > current_time);
+    if config.co_owners.len() + config.pending_co_owners.len() >= MAX_CO_OWNERS {
+        return Err(VaultError::InvalidInput(format!("A vault can have at most {} co-owners", MAX_CO_OWNERS)));
+    }
+
+    let proposal = CoOwnerProposal {
+        principal,
+        permissions,
+        proposed_at: current_time,
+        expires_at: current_time.saturating_add(CO_OWNER_PROPOSAL_EXPIRY_NANOS),
+    };
+    config.pending_co_owners.push(proposal.clone());
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::CoOwnerProposed, caller, format!("Co-ownership offered to {} with {:?}", principal, proposal.permissions));
+    notification_service::notify_principal(
+        vault_id,
+        principal,
+        NotificationKind::CoOwnerProposed { proposed_by: caller, expires_at: proposal.expires_at },
+        format!("co_owner_proposed:{}:{}:{}", vault_id, principal, current_time),
+    );
+    Ok(proposal)
+}
+
+/// Accepts a co-ownership offered to the caller. Any membership the caller held is given up.
+pub fn accept_co_owner(vault_id: &VaultId, caller: PrincipalId) -> Result<CoOwner, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    let current_time = time();
+    let proposal = config.pending_co_owners.iter()
+        .find(|p| p.principal == caller)
+        .cloned()
+        .ok_or_else(|| VaultError::NotFound("No co-ownership has been offered to the caller".to_string()))?;
+    config.pending_co_owners.retain(|p| p.principal != caller);
+    if current_time > proposal.expires_at {
+        storage::vault_configs::insert_vault_config(&config);
+        return Err(VaultError::InvalidState("The co-ownership offer has expired".to_string()));
+    }
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Cannot add co-owners to a vault in status {:?}", config.status)));
+    }
+    // Their membership is dropped below; weighted unlock thresholds must survive that
+    vault_service::check_member_removal(vault_id, caller)?;
+
+    let co_owner = CoOwner {
+        principal: caller,
+        permissions: proposal.permissions,
+        added_at: current_time,
+        last_active_at: Some(current_time), // The co-owner's inactivity clock starts now
+    };
+    config.co_owners.push(co_owner.clone());
+    config.updated_at = current_time;
+
+    // A co-owner cannot also hold a member role
+    if let Some(member) = storage::members::remove_member(vault_id, &caller) {
+        ic_cdk::print(format!(
+            "ℹ️ INFO: Co-owner {} was a {:?} of vault {}; membership removed.",
+            caller, member.role, vault_id
+        ));
+    }
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::CoOwnerAdded, caller, format!("Co-owner {} accepted with {:?}", caller, co_owner.permissions));
+    Ok(co_owner)
+}
+
+/// Withdraws (primary owner) or declines (invitee) a pending co-ownership offer.
+pub fn cancel_co_owner_proposal(vault_id: &VaultId, principal: PrincipalId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if caller != config.owner && caller != principal {
+        return Err(VaultError::NotAuthorized("Only the owner or the invitee can cancel a co-ownership offer".to_string()));
+    }
+    let before = config.pending_co_owners.len();
+    config.pending_co_owners.retain(|p| p.principal != principal);
+    if config.pending_co_owners.len() == before {
+        return Err(VaultError::NotFound(format!("No co-ownership offer pending for {}", principal)));
+    }
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::CoOwnerProposalCancelled, caller, format!("Co-ownership offer to {} cancelled", principal));
+    Ok(())
+}
+
+/// Removes a co-owner. A co-owner may always remove themselves; the primary owner may remove
+/// others directly unless joint approval is required, in which case it is a joint action.
+pub fn remove_co_owner(vault_id: &VaultId, principal: PrincipalId, caller: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if caller != principal {
+        if config.owner != caller {
+            return Err(VaultError::NotAuthorized("Only the primary owner can remove other co-owners".to_string()));
+        }
+        if vault_service::joint_approval_required(&config) {
+            return Err(VaultError::JointApprovalRequired("Removing a co-owner must be proposed as a joint action".to_string()));
+        }
+    }
+    execute_remove_co_owner(config, principal, caller)
+}
+
+fn execute_remove_co_owner(mut config: VaultConfig, principal: PrincipalId, actor: PrincipalId) -> Result<(), VaultError> {
+    let before = config.co_owners.len();
+    config.co_owners.retain(|c| c.principal != principal);
+    if config.co_owners.len() == before {
+        return Err(VaultError::NotFound(format!("{} is not a co-owner of vault {}", principal, config.vault_id)));
+    }
+    // Approvals by the departing owner no longer count
+    for request in config.pending_joint_actions.iter_mut() {
+        request.approvals.retain(|p| *p != principal);
+    }
+    config.pending_joint_actions.retain(|a| a.proposed_by != principal);
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(&config.vault_id, LogAction::CoOwnerRemoved, actor, format!("Co-owner {} removed", principal));
+    Ok(())
+}
+
+/// Replaces a co-owner's permissions. Primary owner only.
+pub fn set_co_owner_permissions(
+    vault_id: &VaultId,
+    principal: PrincipalId,
+    permissions: Vec<OwnerPermission>,
+    caller: PrincipalId,
+) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if config.owner != caller {
+        return Err(VaultError::NotAuthorized("Only the primary owner can change co-owner permissions".to_string()));
+    }
+    let co_owner = config
+        .co_owners
+        .iter_mut()
+        .find(|c| c.principal == principal)
+        .ok_or_else(|| VaultError::NotFound(format!("{} is not a co-owner of vault {}", principal, vault_id)))?;
+    co_owner.permissions = permissions.clone();
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::CoOwnerPermissionsChanged, caller, format!("Co-owner {} permissions set to {:?}", principal, permissions));
+    Ok(())
+}
+
+/// Turns the joint approval requirement on or off. Any owner with settings permission may
+/// turn it on; turning it off while it is in force must be proposed as a joint action.
+pub fn set_joint_approval(vault_id: &VaultId, enabled: bool, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Caller cannot change vault settings".to_string()));
+    }
+    if config.require_joint_approval == enabled {
+        return Ok(());
+    }
+    if !enabled && vault_service::joint_approval_required(&config) {
+        return Err(VaultError::JointApprovalRequired("Disabling joint approval must be proposed as a joint action".to_string()));
+    }
+
+    config.require_joint_approval = enabled;
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+    storage::audit_logs::record(vault_id, LogAction::JointApprovalChanged, caller, format!("Joint approval {}", if enabled { "enabled" } else { "disabled" }));
+    Ok(())
+}
+
+/// Proposes an action that needs every owner's sign-off. The proposer's approval is implied.
+/// Other owners are notified and have 7 days to approve.
+pub fn propose_joint_action(vault_id: &VaultId, action: JointAction, caller: PrincipalId) -> Result<JointActionRequest, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Caller cannot propose changes to this vault".to_string()));
+    }
+    if !vault_service::joint_approval_required(&config) {
+        return Err(VaultError::InvalidState("Vault does not require joint approval; apply the change directly".to_string()));
+    }
+    if let JointAction::RemoveCoOwner(principal) = &action {
+        if !config.co_owners.iter().any(|c| c.principal == *principal) {
+            return Err(VaultError::NotFound(format!("{} is not a co-owner of vault {}", principal, vault_id)));
+        }
+    }
+    if let JointAction::SetApprovalWeight { member, .. } = &action {
+        if !storage::members::is_member(vault_id, member) {
+            return Err(VaultError::MemberNotFound(member.to_string()));
+        }
+    }
+
+    let current_time = time();
+    prune_expired_joint_actions(&mut config, current_time);
+    if config.pending_joint_actions.len() >= MAX_PENDING_JOINT_ACTIONS {
+        return Err(VaultError::InvalidState("Too many pending joint actions".to_string()));
+    }
+
+    // Proposal timestamp is the id; bump it if two proposals land in the same instant
+    let mut id = current_time;
+    while config.pending_joint_actions.iter().any(|a| a.id == id) {
+        id += 1;
+    }
+    let request = JointActionRequest {
+        id,
+        action,
+        proposed_by: caller,
+        approvals: vec![caller],
+        expires_at: current_time + JOINT_ACTION_EXPIRY_NANOS,
+    };
+    config.pending_joint_actions.push(request.clone());
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::JointActionProposed, caller, format!("Joint action {} proposed: {:?}", id, request.action));
+    notification_service::notify_owner(
+        vault_id,
+        NotificationKind::JointActionProposed { action_id: id, proposed_by: caller, expires_at: request.expires_at },
+        format!("joint_action:{}:{}", vault_id, id),
+    );
+    Ok(request)
+}
+
+/// Approves a pending joint action. Once every current owner has approved, the action is
+/// executed on behalf of the proposer. If execution fails the request stays pending with its
+/// approvals, and any owner's approval retries it.
+///
+/// # Returns
+/// * `Result<Option<PaymentSession>, VaultError>` - A payment session if an approved plan upgrade needs payment.
+pub async fn approve_joint_action(vault_id: &VaultId, action_id: u64, caller: PrincipalId) -> Result<Option<PaymentSession>, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only vault owners can approve joint actions".to_string()));
+    }
+
+    let current_time = time();
+    if prune_expired_joint_actions(&mut config, current_time) {
+        storage::vault_configs::insert_vault_config(&config);
+    }
+    let index = config
+        .pending_joint_actions
+        .iter()
+        .position(|a| a.id == action_id)
+        .ok_or_else(|| VaultError::NotFound(format!("Joint action {} not found or expired", action_id)))?;
+
+    let request = &mut config.pending_joint_actions[index];
+    if !request.approvals.contains(&caller) {
+        request.approvals.push(caller);
+        storage::audit_logs::record(vault_id, LogAction::JointActionApproved, caller, format!("Joint action {} approved", action_id));
+    }
+
+    let owners = config.all_owners();
+    let request = config.pending_joint_actions[index].clone();
+    if !owners.iter().all(|o| request.approvals.contains(o)) {
+        storage::vault_configs::insert_vault_config(&config);
+        return Ok(None);
+    }
+
+    // Everyone signed off: consume the request so a concurrent approval cannot run it twice
+    config.pending_joint_actions.remove(index);
+    storage::vault_configs::insert_vault_config(&config);
+
+    match execute_joint_action(&config, request.clone()).await {
+        Ok(result) => {
+            storage::audit_logs::record(vault_id, LogAction::JointActionExecuted, caller, format!("Joint action {} executed: {:?}", action_id, request.action));
+            Ok(result)
+        }
+        Err(e) => {
+            // Put the request back with its sign-offs; approving it again retries the execution
+            if let Some(mut latest) = storage::vault_configs::get_vault_config(vault_id) {
+                if !latest.pending_joint_actions.iter().any(|a| a.id == action_id) {
+                    latest.pending_joint_actions.push(request);
+                    storage::vault_configs::insert_vault_config(&latest);
+                }
+            }
+            storage::audit_logs::record(vault_id, LogAction::JointActionFailed, caller, format!("Joint action {} failed: {:?}", action_id, e));
+            Err(e)
+        }
+    }
+}
+
+async fn execute_joint_action(config: &VaultConfig, request: JointActionRequest) -> Result<Option<PaymentSession>, VaultError> {
+    let vault_id = config.vault_id;
+    let update = |unlock_conditions, plan| VaultUpdateData {
+        name: None,
+        description: config.description.clone(), // None would clear it
+        unlock_conditions,
+        plan,
+        activity_source: None,
+        owner_recovery: None,
+        restore_window_days: None,
+        max_content_versions: None,
+        successor_activation_days: None,
+        challenge_window_days: None,
+    };
+
+    match request.action {
+        JointAction::UpdateUnlockConditions(conditions) => {
+            vault_service::apply_vault_update(&vault_id, update(Some(conditions), None), request.proposed_by).await
+        }
+        JointAction::ChangePlan(plan) => {
+            vault_service::apply_vault_update(&vault_id, update(None, Some(plan)), request.proposed_by).await
+        }
+        JointAction::DeleteVault => {
+            vault_service::execute_delete_vault(&vault_id, request.proposed_by).await?;
+            Ok(None)
+        }
+        JointAction::RemoveCoOwner(principal) => {
+            execute_remove_co_owner(storage::vault_configs::require(&vault_id)?, principal, request.proposed_by)?;
+            Ok(None)
+        }
+        JointAction::SetDisclosureStages(specs) => {
+            disclosure_service::apply_disclosure_stages(&vault_id, specs, request.proposed_by)?;
+            Ok(None)
+        }
+        JointAction::SetApprovalWeight { member, weight } => {
+            vault_service::apply_member_approval_weight(&vault_id, member, weight, request.proposed_by)?;
+            Ok(None)
+        }
+        JointAction::DisableJointApproval => {
+            let mut config = storage::vault_configs::require(&vault_id)?;
+            config.require_joint_approval = false;
+            config.updated_at = time();
+            storage::vault_configs::insert_vault_config(&config);
+            storage::audit_logs::record(&vault_id, LogAction::JointApprovalChanged, request.proposed_by, "Joint approval disabled".to_string());
+            Ok(None)
+        }
+    }
+}
+
+/// Withdraws (proposer or primary owner) or rejects (any other owner) a pending joint action.
+pub fn cancel_joint_action(vault_id: &VaultId, action_id: u64, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only vault owners can cancel joint actions".to_string()));
+    }
+    let before = config.pending_joint_actions.len();
+    config.pending_joint_actions.retain(|a| a.id != action_id);
+    if config.pending_joint_actions.len() == before {
+        return Err(VaultError::NotFound(format!("Joint action {} not found", action_id)));
+    }
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::JointActionCancelled, caller, format!("Joint action {} cancelled", action_id));
+    Ok(())
+}
+
+/// Lists the joint actions awaiting approval. Owners only.
+pub fn get_pending_joint_actions(vault_id: &VaultId, caller: PrincipalId) -> Result<Vec<JointActionRequest>, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only vault owners can view joint actions".to_string()));
+    }
+    prune_expired_joint_actions(&mut config, time());
+    Ok(config.pending_joint_actions)
+}
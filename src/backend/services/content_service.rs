@@ -0,0 +1,381 @@
+// src/backend/services/content_service.rs
+// Content item settings beyond upload: time capsules released to chosen members on a date,
+// independent of the vault unlock, soft deletion with a restore window, and version history.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        notification::{NotificationKind, NotificationRecipient},
+        activity::ActivityKind,
+        vault_config::{OwnerPermission, VaultConfig},
+        vault_content_item::{ContentVersionInfo, VaultContentItem, VersionBlob},
+    },
+    services::{activity_service, notification_service},
+    storage,
+    utils::crypto::calculate_sha256_hex,
+};
+use ic_cdk::api::time;
+
+const MAX_CAPSULE_RECIPIENTS: usize = 20;
+const MAX_INLINE_PAYLOAD_BYTES: usize = 2 * 1024 * 1024; // Same bound as one upload chunk
+
+/// Loads a content item with its internal id.
+fn get_content_item(content_id: &ContentId) -> Result<(u64, VaultContentItem), VaultError> {
+    let internal_id = storage::content::get_internal_content_id(*content_id)
+        .ok_or(VaultError::ContentNotFound(*content_id))?;
+    let item = storage::content::get_content(internal_id).ok_or(VaultError::ContentNotFound(*content_id))?;
+    Ok((internal_id, item))
+}
+
+/// Turns a content item into a time capsule released to `recipients` at `release_at`,
+/// or back into a regular item (`release_at: None`). Owners with content permission.
+pub fn set_time_capsule(
+    vault_id: &VaultId,
+    content_id: &ContentId,
+    release_at: Option<Timestamp>,
+    recipients: Vec<PrincipalId>,
+    caller: PrincipalId,
+) -> Result<VaultContentItem, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageContent) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can schedule time capsules".to_string()));
+    }
+
+    let (internal_id, mut item) = get_content_item(content_id)?;
+    if item.vault_id != *vault_id || item.deleted_at.is_some() {
+        return Err(VaultError::ContentNotFound(*content_id));
+    }
+    if item.released_at.is_some() {
+        return Err(VaultError::InvalidState(format!("Content {} has already been released", content_id)));
+    }
+
+    let current_time = time();
+    match release_at {
+        Some(release_at) => {
+            if release_at <= current_time {
+                return Err(VaultError::InvalidInput("Release time must be in the future".to_string()));
+            }
+            let mut recipients = recipients;
+            recipients.sort();
+            recipients.dedup();
+            if recipients.is_empty() || recipients.len() > MAX_CAPSULE_RECIPIENTS {
+                return Err(VaultError::InvalidInput(format!("A time capsule needs between 1 and {} recipients", MAX_CAPSULE_RECIPIENTS)));
+            }
+            for recipient in &recipients {
+                let active = storage::members::get_member(vault_id, recipient).map_or(false, |m| m.may_receive_content());
+                if !active {
+                    return Err(VaultError::MemberNotFound(recipient.to_string()));
+                }
+            }
+            item.release_at = Some(release_at);
+            item.recipients = recipients;
+            storage::time_capsules::schedule(release_at, *content_id, *vault_id);
+        }
+        None => {
+            item.release_at = None;
+            item.recipients = Vec::new();
+            storage::time_capsules::unschedule(content_id);
+        }
+    }
+    item.updated_at = current_time;
+    storage::content::update_content(internal_id, item.clone())?;
+
+    storage::audit_logs::record(vault_id, LogAction::TimeCapsuleScheduled, caller, format!(
+        "Content {} release set to {:?} for {} recipient(s)",
+        content_id, item.release_at, item.recipients.len()
+    ));
+    Ok(item)
+}
+
+/// Releases time capsules whose date has come and notifies their recipients.
+/// Called by the scheduler.
+pub fn process_time_capsules(current_time: Timestamp) -> Result<(), VaultError> {
+    let due = storage::time_capsules::get_due(current_time);
+    let mut released = 0u32;
+
+    for (content_id, vault_id) in due {
+        if storage::vault_configs::is_on_legal_hold(&vault_id) {
+            continue; // Released once the hold is lifted
+        }
+        storage::time_capsules::unschedule(&content_id);
+        let (internal_id, mut item) = match get_content_item(&content_id) {
+            Ok(found) => found,
+            Err(_) => continue, // Deleted since it was scheduled
+        };
+        if item.released_at.is_some() || item.release_at.map_or(true, |t| t > current_time) {
+            continue;
+        }
+
+        item.released_at = Some(current_time);
+        if let Err(e) = storage::content::update_content(internal_id, item.clone()) {
+            ic_cdk::eprintln!("❌ ERROR: Failed to release time capsule {} of vault {}: {:?}", content_id, vault_id, e);
+            continue;
+        }
+        released += 1;
+
+        storage::audit_logs::record(&vault_id, LogAction::TimeCapsuleReleased, ic_cdk::id(), format!(
+            "Content {} released to {} recipient(s)", content_id, item.recipients.len()
+        ));
+        for recipient in &item.recipients {
+            if let Err(e) = notification_service::enqueue(
+                &vault_id,
+                NotificationKind::TimeCapsuleReleased { content_id, title: item.title.clone() },
+                NotificationRecipient::Principal(*recipient),
+                format!("time_capsule:{}:{}", content_id, recipient),
+            ) {
+                ic_cdk::eprintln!("❌ ERROR: Failed to enqueue time capsule notification for vault {}: {:?}", vault_id, e);
+            }
+        }
+    }
+
+    if released > 0 {
+        ic_cdk::print(format!("💌 INFO: Released {} time capsule(s).", released));
+    }
+    Ok(())
+}
+
+// --- Soft deletion ---
+
+/// Permanently removes a content item and every reference to it.
+fn purge_content(internal_id: u64, item: &VaultContentItem) -> Result<(), VaultError> {
+    storage::content::remove_content(internal_id, item.content_id)?;
+    if let Err(e) = storage::content_index::remove_from_index(&item.vault_id, &item.content_id) {
+        ic_cdk::eprintln!("❌ ERROR: Failed removing content {} from the index of vault {}: {}", item.content_id, item.vault_id, e);
+    }
+    storage::time_capsules::unschedule(&item.content_id);
+    storage::content_trash::remove(&item.content_id);
+    storage::content_versions::remove_item_versions(&item.content_id);
+    Ok(())
+}
+
+/// Moves a content item to the trash: hidden from everyone, off the quota, and purged once the
+/// vault's restore window ends (at once if the window is 0). Owners with content permission.
+pub fn delete_content(vault_id: &VaultId, content_id: &ContentId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageContent) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can delete content".to_string()));
+    }
+    config.check_legal_hold()?;
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Content cannot be deleted in status {:?}", config.status)));
+    }
+
+    let (internal_id, mut item) = get_content_item(content_id)?;
+    if item.vault_id != *vault_id || item.deleted_at.is_some() {
+        return Err(VaultError::ContentNotFound(*content_id));
+    }
+
+    let current_time = time();
+    if config.restore_window_days == 0 {
+        purge_content(internal_id, &item)?;
+        storage::audit_logs::record(vault_id, LogAction::ContentDeleted, caller, format!("Content {} permanently deleted", content_id));
+    } else {
+        item.deleted_at = Some(current_time);
+        item.updated_at = current_time;
+        storage::content::update_content(internal_id, item.clone())?;
+        storage::time_capsules::unschedule(content_id);
+        let purge_at = config.restore_deadline(current_time);
+        storage::content_trash::add(purge_at, *content_id, *vault_id);
+        storage::audit_logs::record(vault_id, LogAction::ContentDeleted, caller, format!("Content {} moved to trash, restorable until {}", content_id, purge_at));
+    }
+
+    config.storage_used_bytes = config.storage_used_bytes.saturating_sub(item.retained_bytes());
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+    Ok(())
+}
+
+/// Takes a content item out of the trash while its restore window is open, if it still fits the quota.
+/// Owners with content permission.
+pub fn restore_content(vault_id: &VaultId, content_id: &ContentId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageContent) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can restore content".to_string()));
+    }
+    if config.status == VaultStatus::Deleted {
+        return Err(VaultError::InvalidState(format!("Vault {} must be restored first", vault_id)));
+    }
+
+    let (internal_id, mut item) = get_content_item(content_id)?;
+    if item.vault_id != *vault_id {
+        return Err(VaultError::ContentNotFound(*content_id));
+    }
+    if item.deleted_at.is_none() {
+        return Err(VaultError::InvalidState(format!("Content {} is not in the trash", content_id)));
+    }
+    let current_time = time();
+    if storage::content_trash::get_purge_at(content_id).map_or(true, |purge_at| current_time >= purge_at) {
+        return Err(VaultError::InvalidState(format!("The restore window of content {} has ended", content_id)));
+    }
+    if item.retained_bytes() > config.storage_quota_bytes.saturating_sub(config.storage_used_bytes) {
+        return Err(VaultError::StorageLimitExceeded);
+    }
+
+    item.deleted_at = None;
+    item.updated_at = current_time;
+    storage::content::update_content(internal_id, item.clone())?;
+    storage::content_trash::remove(content_id);
+    if let (Some(release_at), None) = (item.release_at, item.released_at) {
+        storage::time_capsules::schedule(release_at, *content_id, *vault_id);
+    }
+
+    config.storage_used_bytes = config.storage_used_bytes.saturating_add(item.retained_bytes());
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+    storage::audit_logs::record(vault_id, LogAction::ContentRestored, caller, format!("Content {} restored from trash", content_id));
+    Ok(())
+}
+
+/// Purges trashed content items whose restore window has ended. Called by the scheduler.
+pub fn purge_deleted_content(current_time: Timestamp) -> Result<(), VaultError> {
+    let mut purged = 0u32;
+    for (content_id, vault_id) in storage::content_trash::get_due(current_time) {
+        if storage::vault_configs::is_on_legal_hold(&vault_id) {
+            continue; // Kept in the trash while the vault is held
+        }
+        storage::content_trash::remove(&content_id);
+        let (internal_id, item) = match get_content_item(&content_id) {
+            Ok(found) if found.1.deleted_at.is_some() => found,
+            _ => continue, // Restored or already gone
+        };
+        if let Err(e) = purge_content(internal_id, &item) {
+            ic_cdk::eprintln!("❌ ERROR: Failed to purge content {} of vault {}: {:?}", content_id, vault_id, e);
+            continue;
+        }
+        purged += 1;
+        storage::audit_logs::record(&vault_id, LogAction::ContentDeleted, ic_cdk::id(), format!("Content {} permanently deleted after its restore window", content_id));
+    }
+
+    if purged > 0 {
+        ic_cdk::print(format!("🗑️ INFO: Purged {} trashed content item(s).", purged));
+    }
+    Ok(())
+}
+
+// --- Version history ---
+
+/// Loads a live content item the caller may edit: an owner with content permission, before unlock,
+/// and not yet released as a time capsule.
+fn editable_item(vault_id: &VaultId, content_id: &ContentId, caller: &PrincipalId) -> Result<(VaultConfig, u64, VaultContentItem), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(caller, OwnerPermission::ManageContent) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can edit content".to_string()));
+    }
+    // Edits can push old versions out of the bounded history, so they count as deletions while held
+    config.check_legal_hold()?;
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Content cannot be edited in status {:?}", config.status)));
+    }
+    let (internal_id, item) = get_content_item(content_id)?;
+    if item.vault_id != *vault_id || item.deleted_at.is_some() {
+        return Err(VaultError::ContentNotFound(*content_id));
+    }
+    if item.released_at.is_some() {
+        return Err(VaultError::InvalidState(format!("Content {} has already been released", content_id)));
+    }
+    Ok((config, internal_id, item))
+}
+
+/// Makes `payload` the item's current version. The outgoing payload is kept as a version and the oldest
+/// versions beyond the vault's cap are dropped. Only mutates `config`/`item` (and version storage once the
+/// quota check has passed); the caller saves both.
+fn replace_payload(
+    config: &mut VaultConfig,
+    item: &mut VaultContentItem,
+    payload: Vec<u8>,
+    editor: PrincipalId,
+    current_time: Timestamp,
+) -> Result<(), VaultError> {
+    let mut history = item.history.clone();
+    history.push(ContentVersionInfo {
+        version: item.version,
+        payload_sha256: item.payload_sha256.clone(),
+        payload_size_bytes: item.payload_size_bytes,
+        written_at: item.updated_at,
+        written_by: item.updated_by,
+        replaced_at: current_time,
+    });
+    let excess = history.len().saturating_sub(config.max_content_versions as usize);
+    let pruned: Vec<ContentVersionInfo> = history.drain(..excess).collect();
+    let pruned_bytes = pruned.iter().fold(0u64, |sum, v| sum.saturating_add(v.payload_size_bytes));
+
+    // The outgoing payload stays counted as a version, unless it is dropped right away
+    let new_used = config.storage_used_bytes.saturating_sub(pruned_bytes).saturating_add(payload.len() as u64);
+    if new_used > config.storage_quota_bytes {
+        return Err(VaultError::StorageLimitExceeded);
+    }
+
+    let outgoing = std::mem::replace(&mut item.payload, payload);
+    if history.iter().any(|v| v.version == item.version) {
+        storage::content_versions::insert_version(item.content_id, item.version, VersionBlob { vault_id: item.vault_id, payload: outgoing });
+    }
+    for version in &pruned {
+        storage::content_versions::remove_version(&item.content_id, version.version);
+    }
+
+    item.history = history;
+    item.payload_size_bytes = item.payload.len() as u64;
+    item.payload_sha256 = Some(calculate_sha256_hex(&item.payload));
+    item.version += 1;
+    item.updated_by = Some(editor);
+    item.updated_at = current_time;
+    config.storage_used_bytes = new_used;
+    config.updated_at = current_time;
+    Ok(())
+}
+
+/// Replaces a content item's payload (e.g. an edited letter), keeping the previous one as a version.
+/// Returns the new version number. Owners with content permission.
+pub fn update_content_payload(vault_id: &VaultId, content_id: &ContentId, payload: Vec<u8>, caller: PrincipalId) -> Result<u32, VaultError> {
+    if payload.is_empty() || payload.len() > MAX_INLINE_PAYLOAD_BYTES {
+        return Err(VaultError::InvalidInput(format!("Payload must be between 1 and {} bytes", MAX_INLINE_PAYLOAD_BYTES)));
+    }
+    let (mut config, internal_id, mut item) = editable_item(vault_id, content_id, &caller)?;
+
+    replace_payload(&mut config, &mut item, payload, caller, time())?;
+    storage::content::update_content(internal_id, item.clone())?;
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::ContentUpdated, caller, format!("Content {} updated to version {}", content_id, item.version));
+    activity_service::note_owner_update(vault_id, caller, ActivityKind::ContentEdit);
+    Ok(item.version)
+}
+
+/// Makes a retained version current again, as a new version; the current payload is kept as a version.
+/// Returns the new version number. Owners with content permission.
+pub fn rollback_content(vault_id: &VaultId, content_id: &ContentId, version: u32, caller: PrincipalId) -> Result<u32, VaultError> {
+    let (mut config, internal_id, mut item) = editable_item(vault_id, content_id, &caller)?;
+    let position = item.history.iter().position(|v| v.version == version)
+        .ok_or_else(|| VaultError::NotFound(format!("Version {} of content {} is not retained", version, content_id)))?;
+    let blob = storage::content_versions::get_version(content_id, version)
+        .ok_or_else(|| VaultError::InternalError(format!("Payload of version {} of content {} is missing", version, content_id)))?;
+
+    // The restored payload moves out of the history rather than being copied
+    let restored = item.history.remove(position);
+    config.storage_used_bytes = config.storage_used_bytes.saturating_sub(restored.payload_size_bytes);
+    replace_payload(&mut config, &mut item, blob.payload, caller, time())?;
+    storage::content_versions::remove_version(content_id, version);
+    storage::content::update_content(internal_id, item.clone())?;
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::ContentRolledBack, caller, format!(
+        "Content {} rolled back to version {} (now version {})", content_id, version, item.version
+    ));
+    activity_service::note_owner_update(vault_id, caller, ActivityKind::ContentEdit);
+    Ok(item.version)
+}
+
+/// Lists the retained previous versions of a content item, oldest first. Owners only.
+pub fn list_content_versions(vault_id: &VaultId, content_id: &ContentId, caller: PrincipalId) -> Result<Vec<ContentVersionInfo>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can view content versions".to_string()));
+    }
+    let (_, item) = get_content_item(content_id)?;
+    if item.vault_id != *vault_id || item.deleted_at.is_some() {
+        return Err(VaultError::ContentNotFound(*content_id));
+    }
+    Ok(item.history)
+}
@@ -0,0 +1,308 @@
+// src/backend/services/deletion_service.rs
+// Vault deletion as a tracked job: every per-vault record is purged in bounded batches
+// across timer ticks, then the config is replaced by a permanent tombstone.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        deletion::{DeletionJob, DeletionStatus, DeletionStep, VaultTombstone},
+        vault_config::VaultConfig,
+    },
+    services::vault_service,
+    storage,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+use std::cell::Cell;
+use std::time::Duration;
+
+/// Records removed per batch from the multi-record stores (members, content, versions, tokens, uploads).
+const BATCH_SIZE: usize = 50;
+/// Instructions a single tick may use before handing over to the next tick.
+const TICK_INSTRUCTION_BUDGET: u64 = 5_000_000_000;
+/// A job untouched for this long is considered stalled (its timer was lost to a trap).
+const STALLED_JOB_NANOS: u64 = 60 * 60 * 1_000_000_000;
+
+thread_local! {
+    /// Whether a deletion tick is already armed, so concurrent starts share one timer.
+    static TICK_SCHEDULED: Cell<bool> = Cell::new(false);
+}
+
+/// Marks the vault Deleted and queues its deletion job. Idempotent: returns the running job if there is one.
+/// Callers are responsible for authorization.
+pub(crate) fn start_deletion(vault_id: &VaultId, requested_by: PrincipalId) -> Result<DeletionJob, VaultError> {
+    if let Some(job) = storage::deletion_jobs::get_job(vault_id) {
+        schedule_tick();
+        return Ok(job);
+    }
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+
+    let current_time = time();
+    let job = DeletionJob {
+        vault_id: *vault_id,
+        owner: config.owner,
+        requested_by,
+        previous_status: config.soft_deleted.as_ref().map_or(config.status, |d| d.previous_status),
+        step: DeletionStep::Uploads,
+        records_removed: 0,
+        bytes_freed: 0,
+        started_at: current_time,
+        updated_at: current_time,
+    };
+    vault_service::apply_status_change(&mut config, VaultStatus::Deleted, Some(requested_by))?;
+    storage::deletion_jobs::upsert_job(&job);
+
+    ic_cdk::print(format!("🗑️ INFO: Deletion job started for vault {} by {}", vault_id, requested_by));
+    storage::audit_logs::record(vault_id, LogAction::VaultDeleted, requested_by, "Vault deletion started".to_string());
+    schedule_tick();
+    Ok(job)
+}
+
+/// Arms a zero-delay timer for the next deletion tick, unless one is already armed.
+fn schedule_tick() {
+    if TICK_SCHEDULED.with(|scheduled| scheduled.replace(true)) {
+        return;
+    }
+    ic_cdk_timers::set_timer(Duration::ZERO, || ic_cdk::spawn(run_deletion_tick()));
+}
+
+/// Advances every deletion job until the instruction budget is used up, then re-arms the timer.
+/// Progress is persisted after each batch, so a trap only loses the batch in flight.
+pub async fn run_deletion_tick() {
+    TICK_SCHEDULED.with(|scheduled| scheduled.set(false));
+
+    for mut job in storage::deletion_jobs::get_jobs() {
+        if storage::vault_configs::is_on_legal_hold(&job.vault_id) {
+            continue; // Paused until the hold is released; the scheduler resumes it
+        }
+        loop {
+            if ic_cdk::api::instruction_counter() > TICK_INSTRUCTION_BUDGET {
+                schedule_tick();
+                return;
+            }
+            match advance_job(&mut job).await {
+                Ok(true) => break, // Job finished
+                Ok(false) => {}
+                Err(e) => {
+                    // Left in place; the scheduler retries it once it counts as stalled.
+                    ic_cdk::eprintln!("❌ ERROR: Deletion of vault {} failed at step {:?}: {:?}", job.vault_id, job.step, e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Runs one batch of the job's current step and persists the job. Returns true once the job is finished.
+async fn advance_job(job: &mut DeletionJob) -> Result<bool, VaultError> {
+    if run_step(job).await? {
+        match job.step.next() {
+            Some(next) => job.step = next,
+            None => {
+                finish_job(job);
+                return Ok(true);
+            }
+        }
+    }
+    job.updated_at = time();
+    storage::deletion_jobs::upsert_job(job);
+    Ok(false)
+}
+
+/// Runs one batch of the current step. Returns true once the step has nothing left to remove.
+async fn run_step(job: &mut DeletionJob) -> Result<bool, VaultError> {
+    let vault_id = job.vault_id;
+    let step = job.step;
+    let store_error = |e: String| VaultError::StorageError(format!("Deletion step {:?} failed: {}", step, e));
+    match step {
+        DeletionStep::Uploads => {
+            let removed = storage::uploads::remove_uploads_batch(&vault_id, BATCH_SIZE)?;
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::Content => {
+            let (removed, bytes) = storage::content::remove_content_batch(&vault_id, BATCH_SIZE)?;
+            job.records_removed += removed;
+            job.bytes_freed = job.bytes_freed.saturating_add(bytes);
+            Ok(removed == 0)
+        }
+        DeletionStep::ContentVersions => {
+            // Versions are keyed by content id; the index is only removed in a later step
+            let content_ids: Vec<ContentId> = storage::content_index::get_index(&vault_id)
+                .map_err(store_error)?
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|id| Principal::from_text(id).ok())
+                .collect();
+            let removed = storage::content_versions::remove_versions_batch(&content_ids, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::ContentIndex => storage::content_index::remove_index(&vault_id).await.map(|_| true).map_err(store_error),
+        DeletionStep::TimeCapsules => {
+            let removed = storage::time_capsules::remove_vault_capsules_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::ContentTrash => {
+            let removed = storage::content_trash::remove_vault_entries_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::Receipts => {
+            let removed = storage::receipts::remove_vault_receipts_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::EmergencyAccess => {
+            let removed = storage::emergency_access::remove_grants_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::Tokens => {
+            let removed = storage::tokens::remove_tokens_batch(&vault_id, BATCH_SIZE)?;
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::Members => {
+            let removed = storage::members::remove_members_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::Approvals => {
+            job.records_removed += storage::approvals::remove_approvals(&vault_id);
+            Ok(true)
+        }
+        DeletionStep::Attestations => {
+            let removed = storage::attestations::remove_vault_attestations_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::Activity => {
+            let removed = storage::activity::remove_activity_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::InactivityWarnings => {
+            let removed = storage::inactivity_warnings::remove_warnings_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::RecoveryQr => {
+            job.records_removed += storage::recovery_qr::remove_recovery_qr(&vault_id);
+            Ok(true)
+        }
+        DeletionStep::OwnerRebind => {
+            job.records_removed += storage::owner_recovery::remove_rebind_request(&vault_id);
+            Ok(true)
+        }
+        DeletionStep::Notifications => {
+            let removed = storage::notifications::remove_vault_notifications_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::AuditLogs => {
+            let removed = storage::audit_logs::remove_audit_logs_batch(&vault_id, BATCH_SIZE);
+            job.records_removed += removed;
+            Ok(removed == 0)
+        }
+        DeletionStep::Config => Ok(true),
+    }
+}
+
+/// Removes the vault config, updates metrics and replaces the job with the vault's tombstone.
+fn finish_job(job: &DeletionJob) {
+    let config = storage::vault_configs::remove_vault_config(&job.vault_id);
+    if config.is_some() {
+        if let Err(e) = storage::metrics::decrement_vault_count() {
+            ic_cdk::eprintln!("❌ ERROR: Failed decrementing vault count metric: {:?}", e);
+        }
+        if job.previous_status == VaultStatus::Active {
+            if let Err(e) = storage::metrics::update_active_vault_count(-1) {
+                ic_cdk::eprintln!("❌ ERROR: Failed decrementing active vault metric: {:?}", e);
+            }
+        }
+        if let Err(e) = crate::metrics::update_storage_usage(-(job.bytes_freed.min(i64::MAX as u64) as i64)) {
+            ic_cdk::eprintln!("❌ ERROR: Failed updating storage usage metric: {:?}", e);
+        }
+    } else {
+        ic_cdk::eprintln!("⚠️ WARNING: Vault {} config already removed when its deletion job finished.", job.vault_id);
+    }
+
+    let tombstone = VaultTombstone {
+        vault_id: job.vault_id,
+        owner: job.owner,
+        deleted_by: job.requested_by,
+        plan: config.as_ref().map(|c| c.plan.clone()).unwrap_or_default(),
+        created_at: config.as_ref().map_or(0, |c| c.created_at),
+        requested_at: job.started_at,
+        completed_at: time(),
+        records_removed: job.records_removed,
+        bytes_freed: job.bytes_freed,
+    };
+    storage::deletion_jobs::insert_tombstone(&tombstone);
+    storage::deletion_jobs::remove_job(&job.vault_id);
+    ic_cdk::print(format!(
+        "✅ SUCCESS: Vault {} deleted ({} records, {} bytes).",
+        job.vault_id, job.records_removed, job.bytes_freed
+    ));
+}
+
+/// Re-arms the deletion timer if jobs are pending. Called after an upgrade (timers do not survive it)
+/// and by the scheduler, which also restarts jobs whose timer was lost to a trap.
+pub fn resume_deletion_jobs(current_time: Timestamp) -> Result<(), VaultError> {
+    let jobs = storage::deletion_jobs::get_jobs();
+    if jobs.is_empty() {
+        return Ok(());
+    }
+    if jobs.iter().any(|job| current_time.saturating_sub(job.updated_at) > STALLED_JOB_NANOS) {
+        TICK_SCHEDULED.with(|scheduled| scheduled.set(false));
+    }
+    ic_cdk::print(format!("🗑️ INFO: Resuming {} vault deletion job(s).", jobs.len()));
+    schedule_tick();
+    Ok(())
+}
+
+/// Starts the deletion job of soft-deleted vaults whose restore window has ended. Called by the scheduler.
+pub fn purge_deleted_vaults(current_time: Timestamp) -> Result<(), VaultError> {
+    let mut due: Vec<(VaultId, PrincipalId)> = Vec::new();
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        for (_key, value) in map_ref.borrow().iter() {
+            let config: VaultConfig = value.0;
+            if config.legal_hold.is_some() {
+                continue;
+            }
+            if let Some(deletion) = &config.soft_deleted {
+                if current_time >= config.restore_deadline(deletion.deleted_at) {
+                    due.push((config.vault_id, deletion.deleted_by));
+                }
+            }
+        }
+    });
+
+    for (vault_id, deleted_by) in due {
+        if storage::deletion_jobs::get_job(&vault_id).is_none() {
+            start_deletion(&vault_id, deleted_by)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the progress of a vault deletion. The vault owner or admin only.
+pub fn get_deletion_status(vault_id: &VaultId, caller: PrincipalId) -> Result<DeletionStatus, VaultError> {
+    let is_admin = storage::config::get_admin_principal() == caller;
+    if let Some(job) = storage::deletion_jobs::get_job(vault_id) {
+        if job.owner != caller && !is_admin {
+            return Err(VaultError::NotAuthorized("Only the vault owner or admin can view the deletion".to_string()));
+        }
+        return Ok(DeletionStatus::InProgress(job));
+    }
+    match storage::deletion_jobs::get_tombstone(vault_id) {
+        Some(tombstone) if tombstone.owner == caller || is_admin => Ok(DeletionStatus::Completed(tombstone)),
+        Some(_) => Err(VaultError::NotAuthorized("Only the vault owner or admin can view the deletion".to_string())),
+        None => Err(VaultError::NotFound(format!("No deletion found for vault {}", vault_id))),
+    }
+}
@@ -0,0 +1,192 @@
+// src/backend/services/disclosure_service.rs
+// Staged disclosure after unlock: each stage releases its content items on its own
+// conditions (delay after the vault became unlockable, witness confirmations).
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        disclosure::{DisclosureStage, DisclosureStageSpec, DisclosureStageStatus},
+        notification::NotificationKind,
+        vault_config::{OwnerPermission, VaultConfig},
+    },
+    services::{notification_service, vault_service},
+    storage,
+};
+use ic_cdk::api::time;
+
+const DAY_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const MAX_STAGES: usize = 10;
+const MAX_STAGE_DELAY_DAYS: u32 = 3650;
+
+/// Replaces the vault's disclosure stages. Owners with settings permission, before the vault
+/// becomes unlockable. On jointly approved vaults this must be proposed as a joint action.
+pub fn set_disclosure_stages(vault_id: &VaultId, specs: Vec<DisclosureStageSpec>, caller: PrincipalId) -> Result<Vec<DisclosureStage>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if vault_service::joint_approval_required(&config) {
+        return Err(VaultError::JointApprovalRequired("Disclosure stages must be proposed as a joint action".to_string()));
+    }
+    apply_disclosure_stages(vault_id, specs, caller)
+}
+
+/// Replaces the disclosure stages without the joint approval check (used once a joint action is approved).
+pub(crate) fn apply_disclosure_stages(vault_id: &VaultId, specs: Vec<DisclosureStageSpec>, caller: PrincipalId) -> Result<Vec<DisclosureStage>, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can change disclosure stages".to_string()));
+    }
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Cannot change disclosure stages in status {:?}", config.status)));
+    }
+    if specs.len() > MAX_STAGES {
+        return Err(VaultError::InvalidInput(format!("A vault can have at most {} disclosure stages", MAX_STAGES)));
+    }
+
+    let mut assigned: Vec<ContentId> = Vec::new();
+    for spec in &specs {
+        if spec.name.trim().is_empty() {
+            return Err(VaultError::InvalidInput("Disclosure stage name cannot be empty".to_string()));
+        }
+        if spec.delay_days > MAX_STAGE_DELAY_DAYS {
+            return Err(VaultError::InvalidInput(format!("Stage delay cannot exceed {} days", MAX_STAGE_DELAY_DAYS)));
+        }
+        for content_id in &spec.content_ids {
+            if assigned.contains(content_id) {
+                return Err(VaultError::InvalidInput(format!("Content {} is assigned to more than one stage", content_id)));
+            }
+            let in_vault = storage::content::get_internal_content_id(*content_id)
+                .and_then(storage::content::get_content)
+                .map_or(false, |item| item.vault_id == *vault_id && item.deleted_at.is_none());
+            if !in_vault {
+                return Err(VaultError::ContentNotFound(*content_id));
+            }
+            assigned.push(*content_id);
+        }
+    }
+
+    config.disclosure_stages = specs
+        .into_iter()
+        .enumerate()
+        .map(|(i, spec)| DisclosureStage {
+            stage_id: i as u32 + 1,
+            spec,
+            witness_confirmations: Vec::new(),
+            status: DisclosureStageStatus::Locked,
+            released_at: None,
+        })
+        .collect();
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::DisclosureStagesUpdated, caller, format!("{} disclosure stage(s) defined", config.disclosure_stages.len()));
+    Ok(config.disclosure_stages)
+}
+
+/// Records a witness confirmation for a stage and releases it if its conditions are now met.
+pub fn confirm_disclosure_stage(vault_id: &VaultId, stage_id: u32, caller: PrincipalId) -> Result<DisclosureStage, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    let is_witness = storage::members::get_member(vault_id, &caller)
+        .map_or(false, |m| m.role == Role::Witness && m.is_participating());
+    if !is_witness {
+        return Err(VaultError::NotAuthorized("Only a witness can confirm a disclosure stage".to_string()));
+    }
+    if !matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked) {
+        return Err(VaultError::InvalidState("Disclosure stages can only be confirmed once the vault is unlockable".to_string()));
+    }
+
+    let stage = config
+        .disclosure_stages
+        .iter_mut()
+        .find(|s| s.stage_id == stage_id)
+        .ok_or_else(|| VaultError::NotFound(format!("Disclosure stage {} not found", stage_id)))?;
+    if !stage.witness_confirmations.contains(&caller) {
+        stage.witness_confirmations.push(caller);
+        storage::audit_logs::record(vault_id, LogAction::DisclosureStageConfirmed, caller, format!("Disclosure stage {} confirmed", stage_id));
+    }
+
+    advance_and_save(&mut config, time());
+    config
+        .disclosure_stages
+        .into_iter()
+        .find(|s| s.stage_id == stage_id)
+        .ok_or_else(|| VaultError::InternalError("Disclosure stage vanished".to_string()))
+}
+
+/// Releases every locked stage whose conditions are met. Only mutates `config`;
+/// returns the ids of the stages released by this call.
+pub(crate) fn advance_disclosure_stages(config: &mut VaultConfig, current_time: Timestamp) -> Vec<u32> {
+    let unlockable_at = match config.unlockable_at {
+        Some(t) if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked) => t,
+        _ => return Vec::new(),
+    };
+
+    let mut released = Vec::new();
+    for stage in config.disclosure_stages.iter_mut() {
+        if stage.status != DisclosureStageStatus::Locked {
+            continue;
+        }
+        let delay_over = current_time >= unlockable_at.saturating_add(stage.spec.delay_days as u64 * DAY_NANOS);
+        let confirmed = stage.witness_confirmations.len() as u32 >= stage.spec.required_witness_confirmations;
+        if delay_over && confirmed {
+            stage.status = DisclosureStageStatus::Unlockable;
+            stage.released_at = Some(current_time);
+            released.push(stage.stage_id);
+        }
+    }
+    released
+}
+
+/// Advances the stages of `config`, saving it and announcing any release.
+fn advance_and_save(config: &mut VaultConfig, current_time: Timestamp) {
+    let released = advance_disclosure_stages(config, current_time);
+    storage::vault_configs::insert_vault_config(config);
+    announce_releases(config, &released);
+}
+
+/// Audits and notifies heirs of newly released stages.
+pub(crate) fn announce_releases(config: &VaultConfig, released: &[u32]) {
+    for stage in config.disclosure_stages.iter().filter(|s| released.contains(&s.stage_id)) {
+        ic_cdk::print(format!("📖 INFO: Vault {} disclosure stage {} ({}) released.", config.vault_id, stage.stage_id, stage.spec.name));
+        storage::audit_logs::record(&config.vault_id, LogAction::DisclosureStageReleased, ic_cdk::id(), format!("Disclosure stage {} ({}) released", stage.stage_id, stage.spec.name));
+        notification_service::notify_heirs(
+            &config.vault_id,
+            NotificationKind::DisclosureStageReleased { stage_id: stage.stage_id, name: stage.spec.name.clone() },
+            &format!("disclosure_stage:{}:{}", config.vault_id, stage.stage_id),
+        );
+    }
+}
+
+/// Releases delayed stages that have come due. Called by the scheduler.
+pub fn process_disclosure_stages(current_time: Timestamp) -> Result<(), VaultError> {
+    let mut due: Vec<VaultConfig> = Vec::new();
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        for (_key, value) in map_ref.borrow().iter() {
+            let config: VaultConfig = value.0;
+            if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked)
+                && config.legal_hold.is_none()
+                && config.disclosure_stages.iter().any(|s| s.status == DisclosureStageStatus::Locked)
+            {
+                due.push(config);
+            }
+        }
+    });
+
+    for mut config in due {
+        let released = advance_disclosure_stages(&mut config, current_time);
+        if !released.is_empty() {
+            storage::vault_configs::insert_vault_config(&config);
+            announce_releases(&config, &released);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the vault's disclosure stages. Owners and members.
+pub fn get_disclosure_stages(vault_id: &VaultId, caller: PrincipalId) -> Result<Vec<DisclosureStage>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) && !storage::members::is_member(vault_id, &caller) {
+        return Err(VaultError::NotAuthorized("Caller is not a member of this vault".to_string()));
+    }
+    Ok(config.disclosure_stages)
+}
@@ -41,12 +41,15 @@
 pub mod invite_service {
 
     use crate::models::vault_invite_token::{TokenStatus, VaultInviteToken, VaultInviteRequest};
-    use crate::models::vault_member::{AccessControl, VaultMember};
-    use crate::models::vault_config::VaultConfig;
+    use crate::models::vault_member::{AccessControl, VaultMember, DEFAULT_APPROVAL_WEIGHT};
+    use crate::models::vault_config::{ManagerPermission, OwnerPermission, VaultConfig};
     use crate::models::common::{Role, VaultStatus, MemberStatus, PrincipalId, ShamirShareIndex, InternalId, InviteTokenId, VaultId};
     use crate::storage::{tokens, members, vault_configs};
+    use crate::models::notification::NotificationKind;
+    use crate::services::{notification_service, recovery_service, setup_service};
     use crate::error::VaultError;
     use crate::utils; // Using rng::with_internal_rng, time::get_current_time_ns, crypto::generate_unique_principal
+    use crate::utils::guards;
     use ic_cdk::print;
     use candid::Principal;
     use sharks::{Sharks, Share};
@@ -86,12 +89,38 @@ pub mod invite_service {
             .ok_or_else(|| VaultError::InternalError(format!("No available Shamir indices left for vault {}", vault_id)))
     }
 
+    // Helper to get the share data a member received with their claimed invite.
+    // Successors claim a copy of it, so this also covers heirs later in a succession chain.
+    fn claimed_share_of(vault_id: &VaultId, heir: &VaultMember) -> Result<Vec<u8>, VaultError> {
+        tokens::get_tokens_by_vault(vault_id)
+            .into_iter()
+            .find(|t| t.status == TokenStatus::Claimed
+                && t.claimed_by == Some(heir.member_id)
+                && t.shamir_share_index == heir.shamir_share_index
+                && !t.share_data.is_empty())
+            .map(|t| t.share_data)
+            .ok_or_else(|| VaultError::InvalidState(format!("No share on record for heir {}", heir.member_id)))
+    }
+
     /// Generates a new invitation token for a specific vault and role.
     pub async fn generate_invite(
         vault_id: VaultId,
         role: Role,
         inviter: PrincipalId,
         req: VaultInviteRequest
+    ) -> Result<(InviteTokenId, Vec<u8>), VaultError> {
+        issue_invite(vault_id, role, inviter, req, None).await
+    }
+
+    /// Issues an invitation token. A successor invite (`successor_of` set) reuses the heir's
+    /// Shamir index and the share data from the heir's claimed invite, so the successor's share
+    /// combines with the other members' shares once activated in the heir's place.
+    pub(crate) async fn issue_invite(
+        vault_id: VaultId,
+        role: Role,
+        inviter: PrincipalId,
+        req: VaultInviteRequest,
+        successor_of: Option<&VaultMember>
     ) -> Result<(InviteTokenId, Vec<u8>), VaultError> {
         print(format!("generate_invite called for vault {} by inviter {}", vault_id, inviter));
 
@@ -100,7 +129,9 @@ pub mod invite_service {
             Ok(_config) => _config,
             Err(e) => return Err(VaultError::VaultNotFound(e))
         };
-        if config.owner != inviter {
+        // The inviter receives the Shamir share, so managers cannot issue invites; they only
+        // resend or revoke the ones an owner created
+        if !config.has_owner_permission(&inviter, OwnerPermission::ManageMembers) {
             return Err(VaultError::NotAuthorized("Only vault owner can generate invites".to_string()));
         }
         if !matches!(config.status, VaultStatus::NeedSetup | VaultStatus::Active) {
@@ -115,9 +146,15 @@ pub mod invite_service {
         }
         print(format!("Vault {} checks passed. SSS Params t={}, n={}", vault_id, threshold, total_shares));
 
-        // --- 2. Get Secret & Shamir Index ---
-        let secret_bytes = get_vault_secret_for_sharing_bytes(&vault_id)?;
-        let shamir_index = get_next_available_shamir_index(&vault_id, total_shares)?;
+        // --- 2. Get Secret & Shamir Index (managers hold no share) ---
+        // Successors get no fresh share: a new polynomial would not combine with the other shares
+        let (secret_bytes, shamir_index) = if role == Role::Manager {
+            (Vec::new(), 0)
+        } else if let Some(heir) = successor_of {
+            (Vec::new(), heir.shamir_share_index)
+        } else {
+            (get_vault_secret_for_sharing_bytes(&vault_id)?, get_next_available_shamir_index(&vault_id, total_shares)?)
+        };
         print(format!("Using Shamir index {} for new invite", shamir_index));
 
         // --- 3. Generate Token IDs ---
@@ -125,8 +162,12 @@ pub mod invite_service {
         let external_principal_id: InviteTokenId = utils::crypto::generate_unique_principal().await?;
         print(format!("Generated token IDs: internal={}, external={}", internal_id, external_principal_id));
 
-        // --- 4. Split Secret using sharks ---
-        let share_bytes = utils::rng::with_internal_rng(|rng| { // Use the helper to access global RNG
+        // --- 4. Split Secret using sharks (or copy the heir's share for a successor) ---
+        let share_bytes = if role == Role::Manager {
+            Vec::new()
+        } else if let Some(heir) = successor_of {
+            claimed_share_of(&vault_id, heir)?
+        } else { utils::rng::with_internal_rng(|rng| { // Use the helper to access global RNG
             let sharks_instance = Sharks(threshold);
             let dealer = sharks_instance.dealer_rng(&secret_bytes, rng);
 
@@ -138,7 +179,7 @@ pub mod invite_service {
 
             // Serialize the share to bytes using From trait
             Ok(Vec::from(specific_share))
-        })?; // Propagate potential error from Ok/Err wrapping
+        })? }; // Propagate potential error from Ok/Err wrapping
         print(format!("Secret split, generated share of size {} bytes", share_bytes.len()));
 
         // --- 5. Create & Store Token ---
@@ -160,10 +201,19 @@ pub mod invite_service {
             share_data: share_bytes.clone(),
             claimed_by: None,
             claimed_at: None,
+            successor_of: successor_of.map(|heir| heir.member_id),
         };
+        let invitee_email = token_data.email.clone();
         tokens::insert_token(internal_id, token_data, external_principal_id)?;
         print(format!("Invite token {} stored successfully", external_principal_id));
 
+        notification_service::notify_email(
+            &vault_id,
+            &invitee_email,
+            NotificationKind::InviteCreated { token_id: external_principal_id, role, expires_at },
+            format!("invite_created:{}", external_principal_id),
+        );
+
         // --- 6. Return ---
         Ok((external_principal_id, share_bytes))
     }
@@ -200,7 +250,11 @@ pub mod invite_service {
         print(format!("Claimer {} is not already a member of vault {}", claimer, token.vault_id));
 
         // --- 4. Create & Store Member ---
-        let acl = AccessControl::default();
+        let acl = AccessControl {
+            download_limit_per_day: vault_configs::get_vault_config(&token.vault_id)
+                .map_or(AccessControl::default().download_limit_per_day, |c| c.post_unlock.daily_download_limit),
+            ..AccessControl::default()
+        };
         let internal_id: InternalId = tokens::get_next_token_id()?;
         let new_member = VaultMember {
             internal_id: Some(internal_id),
@@ -210,28 +264,34 @@ pub mod invite_service {
             relation: token.relation.clone(),
             email: Some(token.email.clone()),
             role: token.role.clone(),
-            status: MemberStatus::Active, // Assuming Active is the status post-claim
+            // Successors wait dormant until activated in place of their heir
+            status: if token.successor_of.is_some() { MemberStatus::Dormant } else { MemberStatus::Active },
             added_at: current_time_ns,
             updated_at: current_time_ns,
             shamir_share_index: token.shamir_share_index.clone(), // Assign index from token
             has_approved_unlock: false,
-            access_control: acl
+            access_control: acl,
+            successor_of: token.successor_of,
+            approval_weight: DEFAULT_APPROVAL_WEIGHT,
         };
         members::insert_member(&new_member);
         print(format!("Stored new member {} for vault {}", claimer, token.vault_id.clone()));
 
+        // The first heir or witness joining retires any outstanding recovery QR
+        recovery_service::on_member_joined(&token.vault_id, &new_member);
+
         // --- 5. Update Token Status (Instead of Removing) ---
         token.status = TokenStatus::Claimed;
         token.claimed_by = Some(claimer);
         token.claimed_at = Some(current_time_ns);
+        let vault_id = token.vault_id;
         // Update the token in storage - Assuming insert overwrites
         tokens::insert_token(internal_id, token, token_principal)?;
         print(format!("Updated token {} status to Claimed", token_principal));
 
         // --- 6. Post-Claim Actions ---
-        // Placeholder: Logic to check quorum, update vault status, etc.,
-        // should likely reside in VaultService or be triggered via an event/callback.
-        print("Post-claim actions (e.g., quorum check) skipped in InviteService");
+        // A newly claimed heir or witness may complete the vault's setup checklist
+        setup_service::advance_setup(&vault_id, claimer);
 
         // --- 7. Return ---
         Ok(new_member)
@@ -256,8 +316,11 @@ pub mod invite_service {
             Ok(_config) => _config,
             Err(e) => return Err(VaultError::VaultNotFound(e))
         };
-        if config.owner != revoker {
-            return Err(VaultError::NotAuthorized("Only vault owner can revoke invites".to_string()));
+        if !guards::has_delegated_permission(&config, &revoker, ManagerPermission::ManageInvites) {
+            return Err(VaultError::NotAuthorized("Only vault owner or manager can revoke invites".to_string()));
+        }
+        if matches!(token.role, Role::Manager | Role::Master) && !config.has_owner_permission(&revoker, OwnerPermission::ManageMembers) {
+            return Err(VaultError::NotAuthorized("Only vault owner can revoke manager invites".to_string()));
         }
         print("Revoker permission check passed");
 
@@ -276,6 +339,40 @@ pub mod invite_service {
         Ok(())
     }
 
+    /// Sends the invite notification for a pending token again (e.g. the invitee lost the email).
+    pub fn resend_invite(
+        token_principal: InviteTokenId,
+        requester: PrincipalId
+    ) -> Result<(), VaultError> {
+        print(format!("resend_invite called for token {} by requester {}", token_principal, requester));
+
+        let internal_id = tokens::get_internal_token_id(token_principal)
+            .ok_or(VaultError::InviteNotFound)?;
+        let token = tokens::get_token(internal_id)
+            .ok_or(VaultError::InviteNotFound)?;
+        let config = vault_configs::require(&token.vault_id)?;
+        if !guards::has_delegated_permission(&config, &requester, ManagerPermission::ResendNotifications) {
+            return Err(VaultError::NotAuthorized("Only vault owner or manager can resend invites".to_string()));
+        }
+        if token.status != TokenStatus::Issued {
+            return Err(VaultError::InvalidState("Invite token already claimed or revoked".to_string()));
+        }
+        let current_time_ns = utils::time::get_current_time_ns();
+        if current_time_ns > token.expires_at {
+            return Err(VaultError::InviteExpired);
+        }
+
+        // A fresh key so the relay delivers it again, at most once a minute
+        notification_service::notify_email(
+            &token.vault_id,
+            &token.email,
+            NotificationKind::InviteCreated { token_id: token.token_id, role: token.role, expires_at: token.expires_at },
+            format!("invite_resent:{}:{}", token.token_id, current_time_ns / 60_000_000_000),
+        );
+        print(format!("Invite notification for token {} re-sent", token_principal));
+        Ok(())
+    }
+
     /// Lists all members for a given vault.
     pub fn list_members(
         vault_id: VaultId,
@@ -289,7 +386,7 @@ pub mod invite_service {
             Err(e) => return Err(VaultError::VaultNotFound(e))
         };
         // Allow owner or any existing member to list members
-        if config.owner != requester && !members::is_member(&vault_id, &requester) {
+        if !config.is_owner(&requester) && !members::is_member(&vault_id, &requester) {
             return Err(VaultError::NotAuthorized("Only vault owner or members can list members".to_string()));
         }
         print("Requester permission check passed");
@@ -316,7 +413,7 @@ pub mod invite_service {
             Err(e) => return Err(VaultError::VaultNotFound(e))
         };
         // Allow owner or any existing member to view details
-        if config.owner != requester && !members::is_member(&vault_id, &requester) {
+        if !config.is_owner(&requester) && !members::is_member(&vault_id, &requester) {
             return Err(VaultError::NotAuthorized("Only vault owner or members can view member details".to_string()));
         }
         // Optional stricter check: Allow only self-view unless owner
@@ -3,4 +3,18 @@ pub mod scheduler;
 pub mod upload_service;
 pub mod vault_service;
 pub mod payment_service;
+pub mod activity_service;
+pub mod notification_service;
+pub mod recovery_service;
+pub mod ownership_service;
+pub mod co_owner_service;
+pub mod access_service;
+pub mod disclosure_service;
+pub mod content_service;
+pub mod receipt_service;
+pub mod deletion_service;
+pub mod setup_service;
+pub mod attestation_service;
+pub mod succession_service;
+pub mod challenge_service;
 // pub mod payment_service; // Placeholder for payment logic 
\ No newline at end of file
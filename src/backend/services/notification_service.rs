@@ -0,0 +1,180 @@
+// src/backend/services/notification_service.rs
+// Outbox of notification events handed to the off-chain relay (Cloudflare worker).
+// Events are enqueued by the services that cause them, pulled by the relay and acknowledged
+// once sent. Events pulled but never acknowledged are handed out again after a timeout.
+
+use crate::{
+    error::VaultError,
+    models::{
+        common::*,
+        notification::{NotificationEvent, NotificationId, NotificationKind, NotificationRecipient, NotificationStatus},
+    },
+    storage,
+};
+use ic_cdk::api::time;
+
+const REDELIVERY_TIMEOUT_NANOS: u64 = 15 * 60 * 1_000_000_000; // 15 minutes without an ack
+const MAX_DELIVERY_ATTEMPTS: u32 = 5;
+const MAX_PULL_LIMIT: usize = 100;
+const MAX_ACK_BATCH: usize = 500;
+const FINISHED_RETENTION_NANOS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000; // Keep acked/failed events for 30 days
+
+/// Adds an event to the outbox unless one was already enqueued under the same dedupe key.
+///
+/// # Returns
+/// * `Result<Option<NotificationId>, VaultError>` - The new event ID, or None if it was a duplicate.
+pub fn enqueue(
+    vault_id: &VaultId,
+    kind: NotificationKind,
+    recipient: NotificationRecipient,
+    dedupe_key: String,
+) -> Result<Option<NotificationId>, VaultError> {
+    if storage::notifications::get_id_by_dedupe_key(&dedupe_key).is_some() {
+        return Ok(None);
+    }
+
+    let id = storage::notifications::get_next_notification_id()?;
+    storage::notifications::insert_notification(NotificationEvent {
+        id,
+        vault_id: *vault_id,
+        kind,
+        recipient,
+        dedupe_key,
+        created_at: time(),
+        status: NotificationStatus::Pending,
+        delivery_attempts: 0,
+        last_delivered_at: None,
+        acked_at: None,
+    });
+    Ok(Some(id))
+}
+
+/// Enqueues an event for the vault owner. Failures are logged, never propagated:
+/// a notification must not block the action that caused it.
+pub fn notify_owner(vault_id: &VaultId, kind: NotificationKind, dedupe_key: String) {
+    let config = match storage::vault_configs::get_vault_config(vault_id) {
+        Some(config) => config,
+        None => return,
+    };
+    if let Err(e) = enqueue(vault_id, kind.clone(), NotificationRecipient::Principal(config.owner), dedupe_key.clone()) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to enqueue owner notification for vault {}: {:?}", vault_id, e);
+    }
+    // Co-owners get the same event under a per-principal key
+    for co_owner in &config.co_owners {
+        let key = format!("{}:{}", dedupe_key, co_owner.principal);
+        if let Err(e) = enqueue(vault_id, kind.clone(), NotificationRecipient::Principal(co_owner.principal), key) {
+            ic_cdk::eprintln!("❌ ERROR: Failed to enqueue co-owner notification for vault {}: {:?}", vault_id, e);
+        }
+    }
+}
+
+/// Enqueues an event for every participating heir of the vault (not revoked or dormant).
+/// `dedupe_key` is suffixed with each heir's principal. Failures are logged, never propagated.
+pub fn notify_heirs(vault_id: &VaultId, kind: NotificationKind, dedupe_key: &str) {
+    notify_members(vault_id, &[Role::Heir], kind, dedupe_key);
+}
+
+/// Enqueues an event for every participating member of the vault holding one of `roles`.
+/// `dedupe_key` is suffixed with each member's principal. Failures are logged, never propagated.
+pub fn notify_members(vault_id: &VaultId, roles: &[Role], kind: NotificationKind, dedupe_key: &str) {
+    let members = storage::members::get_members_by_vault(vault_id)
+        .into_iter()
+        .filter(|m| roles.contains(&m.role) && m.is_participating());
+
+    for member in members {
+        if let Err(e) = enqueue(
+            vault_id,
+            kind.clone(),
+            NotificationRecipient::Principal(member.member_id),
+            format!("{}:{}", dedupe_key, member.member_id),
+        ) {
+            ic_cdk::eprintln!("❌ ERROR: Failed to enqueue member notification for vault {}: {:?}", vault_id, e);
+        }
+    }
+}
+
+/// Enqueues an event for a single principal (a member, or the admin). Failures are logged, never propagated.
+pub fn notify_principal(vault_id: &VaultId, principal: PrincipalId, kind: NotificationKind, dedupe_key: String) {
+    if let Err(e) = enqueue(vault_id, kind, NotificationRecipient::Principal(principal), dedupe_key) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to enqueue principal notification for vault {}: {:?}", vault_id, e);
+    }
+}
+
+/// Enqueues an event for an email address (e.g. an invitee that has no principal yet).
+/// Failures are logged, never propagated.
+pub fn notify_email(vault_id: &VaultId, email: &str, kind: NotificationKind, dedupe_key: String) {
+    if email.is_empty() {
+        return;
+    }
+    if let Err(e) = enqueue(vault_id, kind, NotificationRecipient::Email(email.to_string()), dedupe_key) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to enqueue email notification for vault {}: {:?}", vault_id, e);
+    }
+}
+
+/// Hands events to the relay: pending ones, plus delivered ones whose ack did not arrive in time.
+/// Pass `None` to start from the oldest outstanding event, or the last returned ID to page.
+/// Events that exhausted their delivery attempts are marked Failed instead of returned.
+pub fn pull_notifications(after: Option<NotificationId>, limit: usize) -> Vec<NotificationEvent> {
+    let current_time = time();
+    let start = after.map_or(0, |id| id.saturating_add(1));
+    let limit = limit.clamp(1, MAX_PULL_LIMIT);
+
+    let due = storage::notifications::get_outstanding_from(start, limit, |event| match event.status {
+        NotificationStatus::Pending => true,
+        NotificationStatus::Delivered => event.last_delivered_at
+            .map_or(true, |t| current_time >= t.saturating_add(REDELIVERY_TIMEOUT_NANOS)),
+        NotificationStatus::Acked | NotificationStatus::Failed => false,
+    });
+
+    let mut pulled = Vec::with_capacity(due.len());
+    for mut event in due {
+        if event.delivery_attempts >= MAX_DELIVERY_ATTEMPTS {
+            ic_cdk::eprintln!(
+                "⚠️ WARNING: Notification {} ({:?}) not acknowledged after {} attempts, marking failed.",
+                event.id, event.kind, event.delivery_attempts
+            );
+            event.status = NotificationStatus::Failed;
+            storage::notifications::update_notification(event);
+            continue;
+        }
+        event.status = NotificationStatus::Delivered;
+        event.delivery_attempts += 1;
+        event.last_delivered_at = Some(current_time);
+        storage::notifications::update_notification(event.clone());
+        pulled.push(event);
+    }
+    pulled
+}
+
+/// Marks delivered events as acknowledged by the relay. Unknown or already finished IDs are ignored.
+///
+/// # Returns
+/// * `Result<u32, VaultError>` - The number of events acknowledged.
+pub fn ack_notifications(ids: Vec<NotificationId>) -> Result<u32, VaultError> {
+    if ids.len() > MAX_ACK_BATCH {
+        return Err(VaultError::InvalidInput(format!("At most {} notifications can be acknowledged at once", MAX_ACK_BATCH)));
+    }
+
+    let current_time = time();
+    let mut acked = 0u32;
+    for id in ids {
+        if let Some(mut event) = storage::notifications::get_notification(id) {
+            if event.status != NotificationStatus::Delivered {
+                continue;
+            }
+            event.status = NotificationStatus::Acked;
+            event.acked_at = Some(current_time);
+            storage::notifications::update_notification(event);
+            acked += 1;
+        }
+    }
+    Ok(acked)
+}
+
+/// Drops acknowledged and failed events past the retention window.
+pub fn prune_notifications(current_time: u64) -> Result<(), VaultError> {
+    ic_cdk::print("⚙️ SCHEDULER: Pruning finished notifications...");
+    let removed = storage::notifications::prune_finished(current_time.saturating_sub(FINISHED_RETENTION_NANOS));
+    ic_cdk::print(format!("⚙️ SCHEDULER: Notification prune finished. {} events removed.", removed));
+    Ok(())
+}
@@ -0,0 +1,336 @@
+// src/backend/services/ownership_service.rs
+// Vault ownership changes: owner-initiated two-step transfer, and owner principal
+// recovery (rebind) attested by a quorum of heirs and witnesses.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        notification::{NotificationKind, NotificationRecipient},
+        owner_recovery::{OwnerRebindRequest, OwnerRebindStatus},
+        vault_config::OwnerTransfer,
+    },
+    services::{notification_service, vault_service},
+    storage,
+};
+use ic_cdk::api::time;
+
+const OWNER_TRANSFER_EXPIRY_NANOS: u64 = 7 * 24 * 60 * 60 * 1_000_000_000; // 7 days to accept
+const REBIND_ATTESTATION_WINDOW_NANOS: u64 = 30 * 24 * 60 * 60 * 1_000_000_000; // 30 days to reach quorum
+
+/// Moves the vault to a new owner in one step: the config owner (which also drives the
+/// owner lookup), any pending transfer, and the member roster (the new owner cannot also
+/// be a member). Callers are responsible for authorization and audit entries.
+///
+/// # Returns
+/// * `Result<PrincipalId, VaultError>` - The previous owner.
+pub(crate) fn apply_owner_change(vault_id: &VaultId, new_owner: PrincipalId) -> Result<PrincipalId, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    vault_service::check_member_removal(vault_id, new_owner)?;
+    let previous_owner = config.owner;
+    let current_time = time();
+
+    config.owner = new_owner;
+    config.pending_owner_transfer = None;
+    config.co_owners.retain(|c| c.principal != new_owner);
+    config.pending_co_owners.retain(|p| p.principal != new_owner);
+    config.last_accessed_by_owner = Some(current_time); // The new owner's clock starts now
+    config.updated_at = current_time;
+
+    if let Some(member) = storage::members::remove_member(vault_id, &new_owner) {
+        ic_cdk::print(format!(
+            "ℹ️ INFO: New owner {} was a {:?} of vault {}; membership removed.",
+            new_owner, member.role, vault_id
+        ));
+    }
+    storage::vault_configs::insert_vault_config(&config);
+
+    notification_service::notify_owner(
+        vault_id,
+        NotificationKind::OwnerChanged { from: previous_owner, to: new_owner },
+        format!("owner_changed:{}:{}", vault_id, current_time),
+    );
+    ic_cdk::print(format!("🔁 INFO: Vault {} owner changed from {} to {}.", vault_id, previous_owner, new_owner));
+    Ok(previous_owner)
+}
+
+/// Proposes handing the vault to `new_owner`, replacing any earlier proposal. Owner only.
+pub fn propose_owner_transfer(vault_id: &VaultId, new_owner: PrincipalId, caller: PrincipalId) -> Result<OwnerTransfer, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if config.owner != caller {
+        return Err(VaultError::NotAuthorized("Only the vault owner can transfer ownership".to_string()));
+    }
+    if new_owner == caller || new_owner == PrincipalId::anonymous() {
+        return Err(VaultError::InvalidInput("Invalid new owner".to_string()));
+    }
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Cannot transfer a vault in status {:?}", config.status)));
+    }
+
+    let current_time = time();
+    let transfer = OwnerTransfer {
+        new_owner,
+        proposed_at: current_time,
+        expires_at: current_time.saturating_add(OWNER_TRANSFER_EXPIRY_NANOS),
+    };
+    config.pending_owner_transfer = Some(transfer.clone());
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::OwnerTransferProposed, caller, format!("Ownership transfer proposed to {}", new_owner));
+    if let Err(e) = notification_service::enqueue(
+        vault_id,
+        NotificationKind::OwnerTransferProposed { from: caller, expires_at: transfer.expires_at },
+        NotificationRecipient::Principal(new_owner),
+        format!("owner_transfer_proposed:{}:{}", vault_id, current_time),
+    ) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to enqueue transfer notification for vault {}: {:?}", vault_id, e);
+    }
+    Ok(transfer)
+}
+
+/// Withdraws (owner) or declines (proposed new owner) a pending transfer.
+pub fn cancel_owner_transfer(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    let transfer = config.pending_owner_transfer.clone()
+        .ok_or_else(|| VaultError::NotFound("No pending ownership transfer".to_string()))?;
+    if caller != config.owner && caller != transfer.new_owner {
+        return Err(VaultError::NotAuthorized("Only the owner or the proposed owner can cancel the transfer".to_string()));
+    }
+
+    config.pending_owner_transfer = None;
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::OwnerTransferCancelled, caller, format!("Ownership transfer to {} cancelled", transfer.new_owner));
+    Ok(())
+}
+
+/// Accepts a pending transfer addressed to the caller, making them the owner.
+pub fn accept_owner_transfer(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    let transfer = config.pending_owner_transfer.clone()
+        .ok_or_else(|| VaultError::NotFound("No pending ownership transfer".to_string()))?;
+    if transfer.new_owner != caller {
+        return Err(VaultError::NotAuthorized("This transfer is not addressed to the caller".to_string()));
+    }
+    if time() > transfer.expires_at {
+        config.pending_owner_transfer = None;
+        storage::vault_configs::insert_vault_config(&config);
+        return Err(VaultError::InvalidState("Ownership transfer has expired".to_string()));
+    }
+    // Proposed while the vault was transferable; it may have been unlocked, expired or trashed since
+    if config.soft_deleted.is_some()
+        || matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted)
+    {
+        config.pending_owner_transfer = None;
+        storage::vault_configs::insert_vault_config(&config);
+        return Err(VaultError::InvalidState(format!("Cannot transfer a vault in status {:?}", config.status)));
+    }
+
+    let previous_owner = apply_owner_change(vault_id, caller)?;
+
+    storage::audit_logs::record(vault_id, LogAction::OwnerTransferred, previous_owner, format!("Ownership handed to {}", caller));
+    storage::audit_logs::record(vault_id, LogAction::OwnerTransferAccepted, caller, format!("Ownership accepted from {}", previous_owner));
+    Ok(())
+}
+
+// --- Owner Rebind (lost principal recovery) ---
+
+/// Files a request to rebind the vault's owner to `new_owner`, the owner's new principal.
+/// Filed by the owner from that new principal (`new_owner == caller`), or by a participating
+/// heir or witness on the owner's behalf. Requires an owner recovery quorum to be configured.
+pub fn request_owner_rebind(vault_id: &VaultId, new_owner: PrincipalId, caller: PrincipalId) -> Result<OwnerRebindRequest, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if config.owner_recovery.is_none() {
+        return Err(VaultError::InvalidState("Owner recovery is not configured for this vault".to_string()));
+    }
+    let is_filer = caller == new_owner
+        || storage::members::get_member(vault_id, &caller)
+            .map_or(false, |m| m.is_participating() && matches!(m.role, Role::Heir | Role::Witness));
+    if !is_filer {
+        return Err(VaultError::NotAuthorized("Only the new owner principal, heirs and witnesses can file an owner rebind".to_string()));
+    }
+    if new_owner == config.owner || new_owner == PrincipalId::anonymous() {
+        return Err(VaultError::InvalidInput("Rebind must target a new principal".to_string()));
+    }
+    // Members attest the request, so they cannot also be its beneficiary
+    if storage::members::is_member(vault_id, &new_owner) {
+        return Err(VaultError::InvalidInput("A vault member cannot become the owner through a rebind".to_string()));
+    }
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Cannot rebind the owner of a vault in status {:?}", config.status)));
+    }
+    if storage::owner_recovery::get_rebind_request(vault_id).map_or(false, |r| r.is_open()) {
+        return Err(VaultError::InvalidState("An owner rebind request is already open".to_string()));
+    }
+
+    let current_time = time();
+    let request = OwnerRebindRequest {
+        vault_id: *vault_id,
+        previous_owner: config.owner,
+        new_owner,
+        status: OwnerRebindStatus::Pending,
+        requested_at: current_time,
+        heir_attestations: Vec::new(),
+        witness_attestations: Vec::new(),
+        approved_at: None,
+        executable_at: None,
+        resolved_at: None,
+    };
+    storage::owner_recovery::insert_rebind_request(request.clone());
+
+    storage::audit_logs::record(vault_id, LogAction::OwnerRebindRequested, caller, format!("Owner rebind from {} to {} requested", config.owner, new_owner));
+    let dedupe_key = format!("owner_rebind_requested:{}:{}", vault_id, current_time);
+    let kind = NotificationKind::OwnerRebindRequested { new_owner };
+    notification_service::notify_owner(vault_id, kind.clone(), dedupe_key.clone());
+    notification_service::notify_members(vault_id, &[Role::Heir, Role::Witness], kind, &dedupe_key);
+    Ok(request)
+}
+
+/// Records the caller's (heir or witness) attestation. Once the configured quorum is reached
+/// the request is approved and becomes executable after the veto delay.
+pub fn attest_owner_rebind(vault_id: &VaultId, caller: PrincipalId) -> Result<OwnerRebindRequest, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let recovery_config = config.owner_recovery.clone()
+        .ok_or_else(|| VaultError::InvalidState("Owner recovery is not configured for this vault".to_string()))?;
+    let mut request = storage::owner_recovery::get_rebind_request(vault_id)
+        .filter(|r| r.status == OwnerRebindStatus::Pending)
+        .ok_or_else(|| VaultError::NotFound("No pending owner rebind request".to_string()))?;
+
+    let member = storage::members::get_member(vault_id, &caller)
+        .filter(|m| m.is_participating())
+        .ok_or_else(|| VaultError::NotAuthorized("Only heirs and witnesses can attest".to_string()))?;
+    let attestations = match member.role {
+        Role::Heir => &mut request.heir_attestations,
+        Role::Witness => &mut request.witness_attestations,
+        _ => return Err(VaultError::NotAuthorized("Only heirs and witnesses can attest".to_string())),
+    };
+    if attestations.contains(&caller) {
+        return Err(VaultError::InvalidState("Caller has already attested".to_string()));
+    }
+    attestations.push(caller);
+    storage::audit_logs::record(vault_id, LogAction::OwnerRebindAttested, caller, format!("Attested owner rebind to {}", request.new_owner));
+
+    let quorum_met = request.heir_attestations.len() as u32 >= recovery_config.required_heir_attestations
+        && request.witness_attestations.len() as u32 >= recovery_config.required_witness_attestations;
+    if quorum_met {
+        let current_time = time();
+        let executable_at = current_time.saturating_add(recovery_config.delay_sec.saturating_mul(1_000_000_000));
+        request.status = OwnerRebindStatus::Approved;
+        request.approved_at = Some(current_time);
+        request.executable_at = Some(executable_at);
+
+        storage::audit_logs::record(vault_id, LogAction::OwnerRebindApproved, caller, format!("Quorum reached, executable at {}", executable_at));
+        notification_service::notify_owner(
+            vault_id,
+            NotificationKind::OwnerRebindApproved { new_owner: request.new_owner, executable_at },
+            format!("owner_rebind_approved:{}:{}", vault_id, request.requested_at),
+        );
+    }
+
+    storage::owner_recovery::insert_rebind_request(request.clone());
+    Ok(request)
+}
+
+/// Rejects an open rebind request. Only the current owner principal may veto.
+pub fn veto_owner_rebind(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if config.owner != caller {
+        return Err(VaultError::NotAuthorized("Only the current owner can veto a rebind".to_string()));
+    }
+    let mut request = storage::owner_recovery::get_rebind_request(vault_id)
+        .filter(|r| r.is_open())
+        .ok_or_else(|| VaultError::NotFound("No open owner rebind request".to_string()))?;
+
+    request.status = OwnerRebindStatus::Vetoed;
+    request.resolved_at = Some(time());
+    let new_owner = request.new_owner;
+    storage::owner_recovery::insert_rebind_request(request);
+
+    storage::audit_logs::record(vault_id, LogAction::OwnerRebindVetoed, caller, format!("Owner rebind to {} vetoed", new_owner));
+    Ok(())
+}
+
+/// Executes an approved rebind once its veto delay has passed.
+/// Called by the requester, or by the scheduler for due requests.
+pub fn finalize_owner_rebind(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut request = storage::owner_recovery::get_rebind_request(vault_id)
+        .filter(|r| r.status == OwnerRebindStatus::Approved)
+        .ok_or_else(|| VaultError::NotFound("No approved owner rebind request".to_string()))?;
+    if caller != request.new_owner && caller != ic_cdk::api::id() {
+        return Err(VaultError::NotAuthorized("Only the requester can finalize the rebind".to_string()));
+    }
+    let current_time = time();
+    if request.executable_at.map_or(true, |t| current_time < t) {
+        return Err(VaultError::InvalidState("The veto window has not ended yet".to_string()));
+    }
+    // The owner may have changed by other means (e.g. a transfer) since the request was filed
+    let config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold()?;
+    if config.owner != request.previous_owner {
+        request.status = OwnerRebindStatus::Expired;
+        request.resolved_at = Some(current_time);
+        storage::owner_recovery::insert_rebind_request(request);
+        return Err(VaultError::InvalidState("Vault owner changed since the rebind was requested".to_string()));
+    }
+
+    let previous_owner = apply_owner_change(vault_id, request.new_owner)?;
+    request.status = OwnerRebindStatus::Completed;
+    request.resolved_at = Some(current_time);
+    let new_owner = request.new_owner;
+    storage::owner_recovery::insert_rebind_request(request);
+
+    storage::audit_logs::record(vault_id, LogAction::OwnerRebound, caller, format!("Owner rebound from {} to {}", previous_owner, new_owner));
+    Ok(())
+}
+
+/// Returns the latest rebind request. Visible to the owner, the requester and vault members.
+pub fn get_owner_rebind_request(vault_id: &VaultId, caller: PrincipalId) -> Result<Option<OwnerRebindRequest>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    let request = storage::owner_recovery::get_rebind_request(vault_id);
+    let is_requester = request.as_ref().map_or(false, |r| r.new_owner == caller);
+    if config.owner != caller && !is_requester && !storage::members::is_member(vault_id, &caller) {
+        return Err(VaultError::NotAuthorized("Not allowed to view this vault's rebind request".to_string()));
+    }
+    Ok(request)
+}
+
+/// Scheduler task: executes approved rebinds whose veto window has ended and expires
+/// pending requests that did not reach quorum in time.
+pub fn process_owner_rebinds(current_time: u64) -> Result<(), VaultError> {
+    ic_cdk::print("⚙️ SCHEDULER: Processing owner rebind requests...");
+    let system = ic_cdk::api::id();
+    let mut error_count = 0;
+
+    for mut request in storage::owner_recovery::get_open_rebind_requests() {
+        let vault_id = request.vault_id;
+        if storage::vault_configs::is_on_legal_hold(&vault_id) {
+            continue;
+        }
+        match request.status {
+            OwnerRebindStatus::Approved if request.executable_at.map_or(false, |t| current_time >= t) => {
+                if let Err(e) = finalize_owner_rebind(&vault_id, system) {
+                    ic_cdk::eprintln!("🔥 SCHEDULER ERROR: Failed to finalize owner rebind for vault {}: {:?}", vault_id, e);
+                    error_count += 1;
+                }
+            }
+            OwnerRebindStatus::Pending if current_time > request.requested_at.saturating_add(REBIND_ATTESTATION_WINDOW_NANOS) => {
+                request.status = OwnerRebindStatus::Expired;
+                request.resolved_at = Some(current_time);
+                storage::owner_recovery::insert_rebind_request(request);
+                storage::audit_logs::record(&vault_id, LogAction::OwnerRebindExpired, system, "Owner rebind expired without quorum".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    if error_count > 0 {
+        Err(VaultError::InternalError(format!("{} owner rebinds failed to finalize", error_count)))
+    } else {
+        Ok(())
+    }
+}
@@ -2,8 +2,8 @@
 use crate::{
     error::VaultError,
     models::payment::{store_payment_session, with_payment_session, with_payment_session_mut, PaymentPurpose},
-    models::{billing::BillingEntry, common::VaultStatus, common::*, payment::*},
-    services::vault_service,
+    models::{billing::BillingEntry, common::VaultStatus, common::*, notification::{NotificationKind, NotificationRecipient}, payment::*},
+    services::{notification_service, vault_service},
     storage,
     utils::crypto::generate_unique_principal,
 };
@@ -20,6 +20,7 @@ use std::time::Duration;
 
 // Constants
 const PAYMENT_SESSION_TIMEOUT_SECONDS: u64 = 30 * 60; // 30 minutes
+const PAYMENT_SESSION_RETENTION_SECONDS: u64 = 7 * 24 * 60 * 60; // Finished sessions are kept for 7 days
 const ICP_LEDGER_CANISTER_ID_STR: &str = "ryjl3-tyaaa-aaaaa-aaaba-cai"; // Mainnet ICP Ledger
 
 // --- Helper: Derive Subaccount ---
@@ -86,6 +87,15 @@ pub async fn verify_payment(
     let current_time = time();
     let mut session = with_payment_session(session_id, |s| Ok(s.clone()))?;
 
+    // A renewal pays for the vault it was priced and authorised for, nothing else
+    if let PaymentPurpose::VaultRenewal { vault_id: renewed_vault, .. } = &session.purpose {
+        if renewed_vault != vault_id {
+            return Err(VaultError::PaymentError(format!(
+                "Payment session {} renews vault {}, not {}", session_id, renewed_vault, vault_id
+            )));
+        }
+    }
+
     // 1. Check Session State and Expiry
     match session.state {
         PayState::Confirmed | PayState::Closed => {
@@ -186,6 +196,13 @@ async fn trigger_post_confirmation_actions(
                 ));
                 vault_service::finalize_plan_change(&vault_id, new_plan).await
             }
+            PaymentPurpose::VaultRenewal { vault_id: renewed_vault, years } => {
+                ic_cdk::print(format!(
+                    "INFO: Attempting to renew vault {} for {} year(s)",
+                    renewed_vault, years
+                ));
+                vault_service::finalize_renewal(&renewed_vault, years).await
+            }
         }
     };
 
@@ -198,6 +215,7 @@ async fn trigger_post_confirmation_actions(
             tx_type: match session.purpose {
                 PaymentPurpose::InitialVaultCreation => "Vault Creation".to_string(),
                 PaymentPurpose::PlanUpgrade { ref new_plan } => format!("Plan Upgrade to {}", new_plan),
+                PaymentPurpose::VaultRenewal { years, .. } => format!("Vault Renewal ({} years)", years),
             },
             amount_icp_e8s: amount_e8s_clone,
             payment_method: format!("{:?}", PayMethod::IcpDirect),
@@ -239,6 +257,15 @@ async fn trigger_post_confirmation_actions(
         // Don't return error here, as the core vault action succeeded, but log it.
     }
 
+    if let Err(e) = notification_service::enqueue(
+        &vault_id,
+        NotificationKind::PaymentConfirmed { session_id: session.session_id, amount_e8s: session.amount_e8s },
+        NotificationRecipient::Principal(session.initiating_principal),
+        format!("payment_confirmed:{}", session.session_id),
+    ) {
+        ic_cdk::eprintln!("ERROR: Failed to enqueue payment confirmation for session {}: {:?}", session.session_id, e);
+    }
+
     ic_cdk::print(format!("INFO: Post-confirmation actions completed for vault {}.", vault_id));
     Ok(())
 }
@@ -354,6 +381,23 @@ pub fn close_payment_session(session_id: &PrincipalId) -> Result<(), VaultError>
     })
 }
 
+/// Expires timed-out sessions and drops finished ones past the retention period. Called by the scheduler.
+pub fn process_stale_payment_sessions(current_time: Timestamp) -> Result<(), VaultError> {
+    let expired = expire_payment_sessions(current_time);
+    if !expired.is_empty() {
+        ic_cdk::print(format!("⌛ INFO: Expired {} stale payment session(s).", expired.len()));
+        storage::metrics::record_payment_sessions_expired(expired.len() as u64)
+            .map_err(|e| VaultError::InternalError(format!("Failed to record expired payment sessions: {}", e)))?;
+    }
+
+    let cutoff = current_time.saturating_sub(Duration::from_secs(PAYMENT_SESSION_RETENTION_SECONDS).as_nanos() as u64);
+    let purged = purge_payment_sessions(cutoff);
+    if purged > 0 {
+        ic_cdk::print(format!("🧹 INFO: Purged {} finished payment session(s).", purged));
+    }
+    Ok(())
+}
+
 /// Lists billing entries (admin only).
 pub async fn list_billing_entries(offset: usize, limit: usize) -> Result<(Vec<BillingEntry>, u64), VaultError> {
     let entries = storage::billing::query_billing_entries(offset, limit);
@@ -0,0 +1,142 @@
+// src/backend/services/receipt_service.rs
+// Post-unlock delivery tracking: downloads and heir acknowledgements per content item,
+// summarised in a completion report for the owner and an owner-chosen witness.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        receipt::{ContentReceipt, HeirReceiptReport, ItemReceiptStatus, ReceiptSummary},
+        vault_config::{OwnerPermission, VaultConfig},
+        vault_content_item::VaultContentItem,
+    },
+    services::access_service,
+    storage,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+/// All content items of a vault, in index order.
+fn vault_items(vault_id: &VaultId) -> Vec<VaultContentItem> {
+    storage::content_index::get_index(vault_id)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|id| Principal::from_text(id).ok())
+        .filter_map(storage::content::get_internal_content_id)
+        .filter_map(storage::content::get_content)
+        .filter(|item| item.deleted_at.is_none())
+        .collect()
+}
+
+/// Records a member download. Called from the download path after authorisation.
+pub(crate) fn record_download(vault_id: &VaultId, member: &PrincipalId, content_id: &ContentId, current_time: Timestamp) {
+    storage::receipts::update_receipt(vault_id, member, content_id, |r| {
+        r.first_downloaded_at.get_or_insert(current_time);
+        r.last_downloaded_at = Some(current_time);
+        r.download_count = r.download_count.saturating_add(1);
+    });
+}
+
+/// Confirms the caller received a content item they are allowed to read.
+pub fn acknowledge_receipt(vault_id: &VaultId, content_id: &ContentId, caller: PrincipalId) -> Result<ContentReceipt, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if config.is_owner(&caller) {
+        return Err(VaultError::InvalidInput("Owners do not acknowledge receipt".to_string()));
+    }
+    let item = storage::content::get_internal_content_id(*content_id)
+        .and_then(storage::content::get_content)
+        .filter(|item| item.vault_id == *vault_id && item.deleted_at.is_none())
+        .ok_or(VaultError::ContentNotFound(*content_id))?;
+    if !access_service::can_read_content(&config, &item, &caller) {
+        return Err(VaultError::NotAuthorized(format!("Content {} has not been released to the caller", content_id)));
+    }
+
+    let current_time = time();
+    let receipt = storage::receipts::update_receipt(vault_id, &caller, content_id, |r| {
+        r.acknowledged_at.get_or_insert(current_time);
+    });
+    if receipt.acknowledged_at == Some(current_time) {
+        storage::audit_logs::record(vault_id, LogAction::ContentReceiptAcknowledged, caller, format!("Receipt of content {} acknowledged", content_id));
+    }
+    Ok(receipt)
+}
+
+/// Chooses (or clears) the witness allowed to view the completion report. Owners only.
+pub fn set_receipt_witness(vault_id: &VaultId, witness: Option<PrincipalId>, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageMembers) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can choose the receipt witness".to_string()));
+    }
+    if let Some(witness) = witness {
+        let is_witness = storage::members::get_member(vault_id, &witness)
+            .map_or(false, |m| m.role == Role::Witness && m.is_participating());
+        if !is_witness {
+            return Err(VaultError::InvalidInput(format!("{} is not a witness of vault {}", witness, vault_id)));
+        }
+    }
+
+    config.receipt_witness = witness;
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+    storage::audit_logs::record(vault_id, LogAction::ReceiptWitnessChanged, caller, format!("Receipt witness set to {:?}", witness));
+    Ok(())
+}
+
+/// Builds the per-heir report from content released to each heir and their receipts.
+fn build_report(config: &VaultConfig) -> Vec<HeirReceiptReport> {
+    let items = vault_items(&config.vault_id);
+    storage::members::get_members_by_vault(&config.vault_id)
+        .into_iter()
+        .filter(|m| m.role == Role::Heir && m.is_participating())
+        .map(|heir| {
+            let receipts = storage::receipts::get_receipts(&config.vault_id, &heir.member_id);
+            let lines: Vec<ItemReceiptStatus> = items
+                .iter()
+                .filter(|item| access_service::can_read_content(config, item, &heir.member_id))
+                .map(|item| {
+                    let receipt = receipts.iter().find(|r| r.content_id == item.content_id);
+                    ItemReceiptStatus {
+                        content_id: item.content_id,
+                        title: item.title.clone(),
+                        downloaded_at: receipt.and_then(|r| r.first_downloaded_at),
+                        download_count: receipt.map_or(0, |r| r.download_count),
+                        acknowledged_at: receipt.and_then(|r| r.acknowledged_at),
+                    }
+                })
+                .collect();
+            HeirReceiptReport {
+                heir: heir.member_id,
+                name: heir.name.clone(),
+                complete: !lines.is_empty() && lines.iter().all(|l| l.acknowledged_at.is_some()),
+                items: lines,
+            }
+        })
+        .collect()
+}
+
+/// Returns who has downloaded and acknowledged what. The owner or the receipt witness only.
+pub fn get_completion_report(vault_id: &VaultId, caller: PrincipalId) -> Result<Vec<HeirReceiptReport>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) && config.receipt_witness != Some(caller) {
+        return Err(VaultError::NotAuthorized("Only the owner or the receipt witness can view the completion report".to_string()));
+    }
+    Ok(build_report(&config))
+}
+
+/// Aggregated receipt counts for the vault summary.
+pub fn summarize_receipts(config: &VaultConfig) -> ReceiptSummary {
+    let report = build_report(config);
+    let mut summary = ReceiptSummary { heirs_total: report.len() as u32, ..Default::default() };
+    for heir in &report {
+        if heir.complete {
+            summary.heirs_complete += 1;
+        }
+        summary.items_released += heir.items.len() as u32;
+        summary.items_downloaded += heir.items.iter().filter(|l| l.downloaded_at.is_some()).count() as u32;
+        summary.items_acknowledged += heir.items.iter().filter(|l| l.acknowledged_at.is_some()).count() as u32;
+    }
+    summary
+}
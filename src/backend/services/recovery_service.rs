@@ -0,0 +1,218 @@
+// src/backend/services/recovery_service.rs
+// Recovery QR: a single-use secret, shown once to the owner, that can open the vault
+// when the usual heir/witness quorum is not available.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        recovery_qr::{RecoveryQrInfo, RecoveryQrRecord, RecoveryQrStatus},
+        vault_config::OwnerPermission,
+        vault_member::VaultMember,
+    },
+    services::vault_service,
+    storage,
+    utils::crypto::{calculate_sha256_hex, generate_random_hex_string},
+};
+use ic_cdk::api::time;
+
+const RECOVERY_SECRET_BYTES: usize = 32;
+const MAX_FAILED_ATTEMPTS: u32 = 5; // Per QR, from any callers, before a lockout
+const FAILED_ATTEMPT_LOCKOUT_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// True if any participating heir or witness has joined the vault.
+fn has_heir_or_witness(vault_id: &VaultId) -> bool {
+    storage::members::get_members_by_vault(vault_id)
+        .iter()
+        .any(|m| matches!(m.role, Role::Heir | Role::Witness) && m.is_participating())
+}
+
+/// Issues a new recovery secret for the vault, replacing any previous one. Owners with settings
+/// permission, and only while the vault does not require joint approval.
+/// The secret is returned only here; the canister keeps its hash.
+///
+/// # Returns
+/// * `Result<String, VaultError>` - The hex-encoded secret to render as a QR code.
+pub async fn issue_recovery_qr(vault_id: &VaultId, caller: PrincipalId) -> Result<String, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can issue a recovery QR".to_string()));
+    }
+    // A bypass secret skips the heir/witness quorum, so it needs every owner's sign-off like the unlock rules
+    if vault_service::joint_approval_required(&config) {
+        return Err(VaultError::JointApprovalRequired("Recovery QRs cannot be issued while joint approval is required".to_string()));
+    }
+    let qr_config = config.unlock_conditions.recovery_qr.clone().unwrap_or_default();
+    if !qr_config.enabled {
+        return Err(VaultError::InvalidState("Recovery QR is not enabled for this vault".to_string()));
+    }
+    if !matches!(config.status, VaultStatus::NeedSetup | VaultStatus::SetupComplete | VaultStatus::Active) {
+        return Err(VaultError::InvalidState(format!("Cannot issue a recovery QR in status {:?}", config.status)));
+    }
+    // Members joining revokes the QR, so issuing one afterwards would be pointless
+    if qr_config.valid_if_no_heir_or_witness && has_heir_or_witness(vault_id) {
+        return Err(VaultError::RecoveryQrBlockedPostSetup);
+    }
+
+    let secret = generate_random_hex_string(RECOVERY_SECRET_BYTES).await?;
+    storage::recovery_qr::insert_recovery_qr(RecoveryQrRecord {
+        vault_id: *vault_id,
+        secret_hash: calculate_sha256_hex(secret.as_bytes()),
+        status: RecoveryQrStatus::Active,
+        issued_at: time(),
+        failed_attempts: 0,
+        revoked_at: None,
+        redeemed_at: None,
+        redeemed_by: None,
+        attempts_since_lockout: 0,
+        locked_until: None,
+    });
+
+    storage::audit_logs::record(vault_id, LogAction::RecoveryQrIssued, caller, "Recovery QR issued".to_string());
+    ic_cdk::print(format!("🔑 INFO: Recovery QR issued for vault {}.", vault_id));
+    Ok(secret)
+}
+
+/// Revokes the vault's active recovery QR. Owners with settings permission.
+pub fn revoke_recovery_qr(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can revoke the recovery QR".to_string()));
+    }
+    if vault_service::joint_approval_required(&config) {
+        return Err(VaultError::JointApprovalRequired("Recovery QRs cannot be revoked while joint approval is required".to_string()));
+    }
+    let mut record = storage::recovery_qr::get_recovery_qr(vault_id)
+        .ok_or_else(|| VaultError::NotFound("No recovery QR issued for this vault".to_string()))?;
+    if record.status != RecoveryQrStatus::Active {
+        return Err(VaultError::InvalidState(format!("Recovery QR is already {:?}", record.status)));
+    }
+
+    record.status = RecoveryQrStatus::Revoked;
+    record.revoked_at = Some(time());
+    storage::recovery_qr::insert_recovery_qr(record);
+
+    storage::audit_logs::record(vault_id, LogAction::RecoveryQrRevoked, caller, "Recovery QR revoked by owner".to_string());
+    Ok(())
+}
+
+/// Returns the state of the vault's recovery QR (never the secret). Owner only.
+pub fn get_recovery_qr_info(vault_id: &VaultId, caller: PrincipalId) -> Result<Option<RecoveryQrInfo>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can view the recovery QR".to_string()));
+    }
+    Ok(storage::recovery_qr::get_recovery_qr(vault_id).map(|r| RecoveryQrInfo {
+        status: r.status,
+        issued_at: r.issued_at,
+        failed_attempts: r.failed_attempts,
+        locked_until: r.locked_until,
+    }))
+}
+
+/// Redeems a recovery secret. Wrong secrets count against the QR itself, whoever sends them, so
+/// rotating principals does not buy more guesses: after `MAX_FAILED_ATTEMPTS` the QR accepts no
+/// redemption for a day. It is never revoked for failures, so strangers cannot destroy it. With `bypass_threshold` a valid secret makes the vault
+/// Unlockable directly; otherwise the regular unlock conditions must also be met.
+pub async fn redeem_recovery_qr(vault_id: &VaultId, secret: &str, caller: PrincipalId) -> Result<(), VaultError> {
+    if caller == PrincipalId::anonymous() {
+        return Err(VaultError::NotAuthorized("Anonymous callers cannot redeem a recovery QR".to_string()));
+    }
+    let config = storage::vault_configs::require(vault_id)?;
+    config.check_legal_hold().map_err(|e| reject_redemption(vault_id, caller, e))?;
+    let qr_config = config.unlock_conditions.recovery_qr.clone().unwrap_or_default();
+    if !qr_config.enabled {
+        return Err(reject_redemption(vault_id, caller, VaultError::InvalidState("Recovery QR is not enabled for this vault".to_string())));
+    }
+    let mut record = storage::recovery_qr::get_recovery_qr(vault_id)
+        .ok_or_else(|| reject_redemption(vault_id, caller, VaultError::NotFound("No recovery QR issued for this vault".to_string())))?;
+    if record.status != RecoveryQrStatus::Active {
+        return Err(reject_redemption(vault_id, caller, VaultError::TokenInvalid(format!("Recovery QR is {:?}", record.status))));
+    }
+
+    let current_time = time();
+    if let Some(locked_until) = record.locked_until.filter(|until| current_time < *until) {
+        return Err(reject_redemption(vault_id, caller, VaultError::RateLimitExceeded(format!(
+            "Too many failed attempts; try again after {}", locked_until
+        ))));
+    }
+
+    // 1. Verify the secret
+    if calculate_sha256_hex(secret.as_bytes()) != record.secret_hash {
+        let attempts = record_failed_attempt(&mut record, current_time);
+        storage::recovery_qr::insert_recovery_qr(record);
+
+        storage::audit_logs::record(vault_id, LogAction::RecoveryQrFailedAttempt, caller, format!("Failed attempt {}/{}", attempts, MAX_FAILED_ATTEMPTS));
+        if attempts >= MAX_FAILED_ATTEMPTS {
+            ic_cdk::print(format!("🔒 INFO: Recovery QR redemption for vault {} locked after {} failed attempts.", vault_id, attempts));
+        }
+        return Err(VaultError::TokenInvalid("Recovery secret does not match".to_string()));
+    }
+
+    // 2. Apply the bypass rules
+    if qr_config.valid_if_no_heir_or_witness && has_heir_or_witness(vault_id) {
+        return Err(reject_redemption(vault_id, caller, VaultError::RecoveryQrBlockedPostSetup));
+    }
+    if !qr_config.bypass_threshold {
+        let conditions_met = vault_service::check_unlock_conditions(&config).await
+            .map_err(|e| reject_redemption(vault_id, caller, e))?;
+        if !conditions_met {
+            return Err(reject_redemption(vault_id, caller, VaultError::UnlockConditionsNotMet));
+        }
+    }
+
+    // 3. Open the vault and consume the QR
+    vault_service::set_vault_status(vault_id, VaultStatus::Unlockable, Some(caller)).await
+        .map_err(|e| reject_redemption(vault_id, caller, e))?;
+
+    record.status = RecoveryQrStatus::Redeemed;
+    record.redeemed_at = Some(time());
+    record.redeemed_by = Some(caller);
+    storage::recovery_qr::insert_recovery_qr(record);
+
+    storage::audit_logs::record(vault_id, LogAction::RecoveryQrRedeemed, caller, format!(
+        "Recovery QR redeemed (bypass_threshold: {})", qr_config.bypass_threshold
+    ));
+    ic_cdk::print(format!("🔓 INFO: Recovery QR redeemed for vault {} by {}.", vault_id, caller));
+    Ok(())
+}
+
+/// Audits a redemption refused for any reason other than a wrong secret, and hands the error back.
+fn reject_redemption(vault_id: &VaultId, caller: PrincipalId, error: VaultError) -> VaultError {
+    storage::audit_logs::record(vault_id, LogAction::RecoveryQrRejected, caller, format!("Redemption rejected: {:?}", error));
+    error
+}
+
+/// Counts a wrong secret against the QR, locking it once the limit is reached.
+/// Returns the attempts since the last lockout, including this one.
+fn record_failed_attempt(record: &mut RecoveryQrRecord, current_time: Timestamp) -> u32 {
+    if record.locked_until.map_or(false, |until| current_time >= until) {
+        record.attempts_since_lockout = 0;
+        record.locked_until = None;
+    }
+    record.failed_attempts = record.failed_attempts.saturating_add(1);
+    record.attempts_since_lockout = record.attempts_since_lockout.saturating_add(1);
+    if record.attempts_since_lockout >= MAX_FAILED_ATTEMPTS {
+        record.locked_until = Some(current_time.saturating_add(FAILED_ATTEMPT_LOCKOUT_NANOS));
+    }
+    record.attempts_since_lockout
+}
+
+/// Revokes the active recovery QR once the first heir or witness joins the vault.
+/// Managers and dormant successors do not count.
+pub fn on_member_joined(vault_id: &VaultId, member: &VaultMember) {
+    if !matches!(member.role, Role::Heir | Role::Witness) || !member.is_participating() {
+        return;
+    }
+    let mut record = match storage::recovery_qr::get_recovery_qr(vault_id) {
+        Some(record) if record.status == RecoveryQrStatus::Active => record,
+        _ => return,
+    };
+    record.status = RecoveryQrStatus::Revoked;
+    record.revoked_at = Some(time());
+    storage::recovery_qr::insert_recovery_qr(record);
+
+    storage::audit_logs::record(vault_id, LogAction::RecoveryQrRevoked, member.member_id, "Recovery QR auto-revoked: first heir or witness joined".to_string());
+    ic_cdk::print(format!("🔑 INFO: Recovery QR for vault {} auto-revoked on member join.", vault_id));
+}
@@ -3,11 +3,23 @@
 
 use crate::{
     error::VaultError,
-    models::common::{InviteStatus, VaultStatus},
+    models::common::{InviteStatus, VaultId, VaultStatus},
     services::vault_service,
     storage::{self, Cbor, StorableString},
     models::{VaultConfig, VaultInviteToken},
+    models::inactivity_warning::{InactivityWarning, InactivityWarningKind},
+    models::notification::NotificationKind,
+    models::payment::{self, PaymentPurpose},
     services::upload_service, // To access ACTIVE_UPLOADS
+    services::notification_service,
+    services::ownership_service,
+    services::access_service,
+    services::disclosure_service,
+    services::content_service,
+    services::deletion_service,
+    services::payment_service,
+    services::setup_service,
+    services::succession_service,
 };
 use ic_cdk::api::time;
 use std::time::Duration;
@@ -17,6 +29,10 @@ const DAY_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
 const HOUR_NANOS: u64 = 60 * 60 * 1_000_000_000;
 const FOURTEEN_DAYS_NANOS: u64 = 14 * DAY_NANOS;
 const ONE_YEAR_NANOS: u64 = 365 * DAY_NANOS; // Approximate
+const DEFAULT_INACTIVITY_WARNING_DAYS: [u32; 4] = [30, 14, 7, 1];
+const INVITE_EXPIRY_NOTICE_NANOS: u64 = 6 * HOUR_NANOS;
+const VAULT_EXPIRY_NOTICE_NANOS: u64 = 30 * DAY_NANOS;
+const DRAFT_EXPIRY_NOTICE_NANOS: u64 = 2 * DAY_NANOS;
 
 /// Performs daily maintenance tasks for the entire system.
 /// This function is intended to be called by a timer or an external trigger (e.g., Cloudflare Worker).
@@ -52,9 +68,103 @@ pub async fn perform_daily_maintenance() -> Result<(), VaultError> {
         errors.push(msg);
     }
 
-    // 4. TODO: Compact Audit Logs (if implemented)
+    // 4. Issue Inactivity Warnings (dead-man's switch reminders)
+    if let Err(e) = check_inactivity_warnings(current_time) {
+        let msg = format!("Failed to check inactivity warnings: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 5. Enqueue Upcoming Expiry Notifications (invites, vault plans)
+    if let Err(e) = notify_upcoming_expirations(current_time) {
+        let msg = format!("Failed to enqueue expiry notifications: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 6. Prune Finished Notifications
+    if let Err(e) = notification_service::prune_notifications(current_time) {
+        let msg = format!("Failed to prune notifications: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 7. Execute Due Owner Rebinds / Expire Stale Requests
+    if let Err(e) = ownership_service::process_owner_rebinds(current_time) {
+        let msg = format!("Failed to process owner rebinds: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 8. Activate Emergency Access Grants Past Their Waiting Period
+    if let Err(e) = access_service::process_emergency_access(current_time) {
+        let msg = format!("Failed to process emergency access grants: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 9. Release Delayed Disclosure Stages
+    if let Err(e) = disclosure_service::process_disclosure_stages(current_time) {
+        let msg = format!("Failed to process disclosure stages: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 10. Release Time Capsules
+    if let Err(e) = content_service::process_time_capsules(current_time) {
+        let msg = format!("Failed to release time capsules: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 11. Purge Trashed Content and Vaults Past Their Restore Window
+    if let Err(e) = content_service::purge_deleted_content(current_time) {
+        let msg = format!("Failed to purge trashed content: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+    if let Err(e) = deletion_service::purge_deleted_vaults(current_time) {
+        let msg = format!("Failed to purge trashed vaults: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 12. Restart Stalled Vault Deletion Jobs
+    if let Err(e) = deletion_service::resume_deletion_jobs(current_time) {
+        let msg = format!("Failed to resume deletion jobs: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 13. Expire Stale Payment Sessions, then Unpaid Draft Vaults (drafts with a live session are kept)
+    if let Err(e) = payment_service::process_stale_payment_sessions(current_time) {
+        let msg = format!("Failed to process stale payment sessions: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+    if let Err(e) = expire_draft_vaults(current_time) {
+        let msg = format!("Failed to expire draft vaults: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
 
-    // 5. TODO: Other periodic tasks (e.g., recalculate metrics)
+    // 14. Advance Vaults Whose Setup Checklist Is Met
+    if let Err(e) = setup_service::process_setup_progression(current_time) {
+        let msg = format!("Failed to advance vault setup: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 15. Activate Successors of Heirs Who Ignored an Unlock Request
+    if let Err(e) = succession_service::process_successor_activations(current_time) {
+        let msg = format!("Failed to activate successors: {:?}", e);
+        ic_cdk::eprintln!("🔥 SCHEDULER ERROR: {}", msg);
+        errors.push(msg);
+    }
+
+    // 16. TODO: Compact Audit Logs (if implemented)
+
+    // 17. TODO: Other periodic tasks (e.g., recalculate metrics)
 
     if errors.is_empty() {
         ic_cdk::print("⚙️ SCHEDULER: Daily maintenance completed successfully.");
@@ -120,6 +230,9 @@ pub async fn check_vault_lifecycles(current_time: u64) -> Result<(), VaultError>
         let map = map_ref.borrow();
         for (key, value) in map.iter() {
             let config: VaultConfig = value.0;
+            if config.legal_hold.is_some() {
+                continue; // Held vaults are frozen
+            }
             // Get vault_id from the deserialized config, not the key
             let vault_id: String = config.vault_id.clone();
 
@@ -144,8 +257,17 @@ pub async fn check_vault_lifecycles(current_time: u64) -> Result<(), VaultError>
                         "⏳ SCHEDULER: Vault {} heir grace ended without unlock, marking for deletion.",
                         vault_id
                     ));
-                    // We transition to Deleted status first, actual data removal happens later or by another trigger
-                    transitions.push((vault_id, VaultStatus::Deleted));
+                    // Goes through the trash like other deletions; the job starts when the restore window ends
+                    vault_ids_to_delete.push(vault_id);
+                }
+                VaultStatus::Unlockable | VaultStatus::Unlocked
+                    if config.post_unlock.access_ended(current_time) =>
+                {
+                    ic_cdk::print(format!(
+                        "⏳ SCHEDULER: Vault {} post-unlock access window ended, moving to Expired.",
+                        vault_id
+                    ));
+                    transitions.push((vault_id, VaultStatus::Expired));
                 }
                 VaultStatus::Unlockable => {
                     let unlock_expiry = config.unlocked_at.map_or(0, |t| t.saturating_add(ONE_YEAR_NANOS));
@@ -192,10 +314,11 @@ pub async fn check_vault_lifecycles(current_time: u64) -> Result<(), VaultError>
     // Trigger actual deletion for vaults marked for deletion
     for vault_id in vault_ids_to_delete {
          ic_cdk::print(format!("⚙️ SCHEDULER: Initiating deletion for vault {}.", vault_id));
-         // The delete_vault function needs proper authorization checks.
-         // Assuming system (scheduler) is authorized for now.
-         let system_principal = ic_cdk::api::id(); // Or a designated admin principal
-         if let Err(e) = vault_service::delete_vault(&vault_id, system_principal).await {
+         // Same soft-delete path as owner deletions; owner/admin checks only apply to API callers.
+         let system_principal = ic_cdk::api::id();
+         let result = storage::vault_configs::require(&vault_id)
+             .and_then(|mut config| vault_service::soft_delete_vault(&mut config, system_principal));
+         if let Err(e) = result {
              ic_cdk::eprintln!(
                 "🔥 SCHEDULER ERROR: Failed to delete vault {}: {:?}",
                 vault_id, e
@@ -212,6 +335,205 @@ pub async fn check_vault_lifecycles(current_time: u64) -> Result<(), VaultError>
     }
 }
 
+/// Issues owner reminders as the inactivity deadline approaches, and a notice for heirs once it passes.
+/// Only the tightest crossed threshold is issued, and each crossing only once per deadline;
+/// owner activity moves the deadline, which starts a fresh set of crossings.
+/// NOTE: This iterates the entire map, which can be inefficient.
+pub fn check_inactivity_warnings(current_time: u64) -> Result<(), VaultError> {
+    ic_cdk::print("⚙️ SCHEDULER: Checking inactivity deadlines...");
+    let mut warnings: Vec<InactivityWarning> = Vec::new();
+
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        let map = map_ref.borrow();
+        for (_key, value) in map.iter() {
+            let config: VaultConfig = value.0;
+            if config.legal_hold.is_some() {
+                continue;
+            }
+            if !matches!(
+                config.status,
+                VaultStatus::NeedSetup | VaultStatus::SetupComplete | VaultStatus::Active | VaultStatus::GraceMaster | VaultStatus::GraceHeir
+            ) {
+                continue;
+            }
+            let inactivity_sec = match config.unlock_conditions.inactivity_duration_sec {
+                Some(sec) => sec,
+                None => continue,
+            };
+
+            let last_active = config.owner_activity_reference();
+            let deadline = last_active.saturating_add(inactivity_sec.saturating_mul(1_000_000_000));
+
+            if current_time >= deadline {
+                if !storage::inactivity_warnings::has_crossing(&config.vault_id, deadline, 0, InactivityWarningKind::HeirNotice) {
+                    ic_cdk::print(format!("⏳ SCHEDULER: Vault {} inactivity deadline passed, notifying heirs.", config.vault_id));
+                    warnings.push(InactivityWarning {
+                        vault_id: config.vault_id,
+                        kind: InactivityWarningKind::HeirNotice,
+                        deadline,
+                        days_before: 0,
+                        issued_at: current_time,
+                    });
+                }
+                continue;
+            }
+
+            let schedule = config.unlock_conditions.inactivity_warning_days
+                .clone()
+                .unwrap_or_else(|| DEFAULT_INACTIVITY_WARNING_DAYS.to_vec());
+            let crossed = schedule.into_iter()
+                .filter(|days| current_time >= deadline.saturating_sub(*days as u64 * DAY_NANOS))
+                .min();
+
+            if let Some(days_before) = crossed {
+                if !storage::inactivity_warnings::has_crossing(&config.vault_id, deadline, days_before, InactivityWarningKind::OwnerReminder) {
+                    ic_cdk::print(format!(
+                        "⏳ SCHEDULER: Vault {} is within {} day(s) of its inactivity deadline, reminding owner.",
+                        config.vault_id, days_before
+                    ));
+                    warnings.push(InactivityWarning {
+                        vault_id: config.vault_id,
+                        kind: InactivityWarningKind::OwnerReminder,
+                        deadline,
+                        days_before,
+                        issued_at: current_time,
+                    });
+                }
+            }
+        }
+    });
+
+    let issued = warnings.len();
+    for warning in warnings {
+        let dedupe_key = format!("inactivity:{}:{}:{}", warning.vault_id, warning.deadline, warning.days_before);
+        match warning.kind {
+            InactivityWarningKind::OwnerReminder => notification_service::notify_owner(
+                &warning.vault_id,
+                NotificationKind::InactivityWarning { days_remaining: warning.days_before, deadline: warning.deadline },
+                dedupe_key,
+            ),
+            InactivityWarningKind::HeirNotice => notification_service::notify_heirs(
+                &warning.vault_id,
+                NotificationKind::InactivityThresholdReached { deadline: warning.deadline },
+                &dedupe_key,
+            ),
+        }
+        storage::inactivity_warnings::add_warning(warning);
+    }
+
+    ic_cdk::print(format!("⚙️ SCHEDULER: Inactivity check finished. {} warnings issued.", issued));
+    Ok(())
+}
+
+/// Enqueues notifications for invites about to expire unclaimed and for vault plans nearing expiry.
+/// Dedupe keys include the expiry timestamp, so each expiry is announced once.
+/// NOTE: This iterates the entire token and config maps, which can be inefficient.
+pub fn notify_upcoming_expirations(current_time: u64) -> Result<(), VaultError> {
+    ic_cdk::print("⚙️ SCHEDULER: Checking upcoming expirations...");
+
+    let expiring_invites = storage::tokens::get_tokens_expiring_between(
+        current_time,
+        current_time.saturating_add(INVITE_EXPIRY_NOTICE_NANOS),
+    );
+    for token in &expiring_invites {
+        notification_service::notify_email(
+            &token.vault_id,
+            &token.email,
+            NotificationKind::InviteExpiring { token_id: token.token_id, expires_at: token.expires_at },
+            format!("invite_expiring:{}", token.token_id),
+        );
+    }
+
+    let mut expiring_vaults: Vec<(VaultId, u64)> = Vec::new();
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        let map = map_ref.borrow();
+        for (_key, value) in map.iter() {
+            let config: VaultConfig = value.0;
+            if config.status == VaultStatus::Active
+                && config.legal_hold.is_none()
+                && config.expires_at > current_time
+                && config.expires_at <= current_time.saturating_add(VAULT_EXPIRY_NOTICE_NANOS)
+            {
+                expiring_vaults.push((config.vault_id, config.expires_at));
+            }
+        }
+    });
+    for (vault_id, expires_at) in &expiring_vaults {
+        notification_service::notify_owner(
+            vault_id,
+            NotificationKind::VaultExpiring { expires_at: *expires_at },
+            format!("vault_expiring:{}:{}", vault_id, expires_at),
+        );
+    }
+
+    ic_cdk::print(format!(
+        "⚙️ SCHEDULER: Expiry check finished. {} invites and {} vaults expiring soon.",
+        expiring_invites.len(), expiring_vaults.len()
+    ));
+    Ok(())
+}
+
+/// Removes Draft vaults whose payment never completed within the configured expiry period,
+/// warning the owner shortly before. Removal goes through the regular deletion job.
+/// NOTE: This iterates the entire config map, which can be inefficient.
+pub fn expire_draft_vaults(current_time: u64) -> Result<(), VaultError> {
+    let expiry_days = storage::config::get_draft_expiry_days();
+    if expiry_days == 0 {
+        return Ok(()); // Draft expiry disabled
+    }
+    ic_cdk::print("⚙️ SCHEDULER: Checking unpaid draft vaults...");
+    let expiry_nanos = expiry_days as u64 * DAY_NANOS;
+
+    let mut expiring: Vec<(VaultId, u64)> = Vec::new();
+    let mut expired: Vec<VaultId> = Vec::new();
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        let map = map_ref.borrow();
+        for (_key, value) in map.iter() {
+            let config: VaultConfig = value.0;
+            if config.status != VaultStatus::Draft || config.legal_hold.is_some() {
+                continue;
+            }
+            let expires_at = config.created_at.saturating_add(expiry_nanos);
+            if current_time >= expires_at {
+                // An owner paying right now keeps the draft; it is removed once the session expires
+                if payment::has_live_payment_session(&config.owner, &PaymentPurpose::InitialVaultCreation, current_time) {
+                    continue;
+                }
+                expired.push(config.vault_id);
+            } else if current_time >= expires_at.saturating_sub(DRAFT_EXPIRY_NOTICE_NANOS) {
+                expiring.push((config.vault_id, expires_at));
+            }
+        }
+    });
+
+    for (vault_id, expires_at) in &expiring {
+        notification_service::notify_owner(
+            vault_id,
+            NotificationKind::DraftExpiring { expires_at: *expires_at },
+            format!("draft_expiring:{}:{}", vault_id, expires_at),
+        );
+    }
+
+    let system_principal = ic_cdk::id();
+    let mut removed: u64 = 0;
+    for vault_id in &expired {
+        match deletion_service::start_deletion(vault_id, system_principal) {
+            Ok(_) => removed += 1,
+            Err(e) => ic_cdk::eprintln!("🔥 SCHEDULER ERROR: Failed to remove draft vault {}: {:?}", vault_id, e),
+        }
+    }
+    if removed > 0 {
+        storage::metrics::record_drafts_expired(removed)
+            .map_err(|e| VaultError::InternalError(format!("Failed to record expired drafts: {}", e)))?;
+    }
+
+    ic_cdk::print(format!(
+        "⚙️ SCHEDULER: Draft check finished. {} drafts expiring soon, {} removed.",
+        expiring.len(), removed
+    ));
+    Ok(())
+}
+
 /// Cleans up upload sessions that were started but never finished.
 pub fn cleanup_stale_uploads(current_time: u64) -> Result<(), VaultError> {
     ic_cdk::print("⚙️ SCHEDULER: Cleaning up stale upload sessions...");
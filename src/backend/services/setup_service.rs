@@ -0,0 +1,171 @@
+// src/backend/services/setup_service.rs
+// Setup progression: NeedSetup -> SetupComplete -> Active, driven by a checklist of what the
+// vault still lacks. Advances automatically once the checklist is met, or when the owner finalises.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        setup::{SetupChecklist, SetupRequirement},
+        vault_config::{OwnerPermission, VaultConfig},
+    },
+    services::vault_service,
+    storage,
+};
+use candid::Principal;
+
+/// Whether the vault has at least one content item that is not in the trash.
+fn has_content(vault_id: &VaultId) -> bool {
+    storage::content_index::get_index(vault_id)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|id| Principal::from_text(id).ok())
+        .filter_map(storage::content::get_internal_content_id)
+        .filter_map(storage::content::get_content)
+        .any(|item| item.deleted_at.is_none())
+}
+
+/// Evaluates the setup checklist of a vault.
+pub(crate) fn evaluate(config: &VaultConfig) -> SetupChecklist {
+    let members = storage::members::get_members_by_vault(&config.vault_id);
+    let mut claimed_heirs = 0u32;
+    let mut claimed_witnesses = 0u32;
+    let mut claimed_witness_weight = 0u32;
+    for m in &members {
+        if !matches!(m.status, MemberStatus::Active | MemberStatus::Verified) {
+            continue;
+        }
+        match m.role {
+            Role::Heir => claimed_heirs += 1,
+            Role::Witness => {
+                claimed_witnesses += 1;
+                claimed_witness_weight = claimed_witness_weight.saturating_add(m.approval_weight);
+            }
+            _ => {}
+        }
+    }
+
+    let conditions = &config.unlock_conditions;
+    let unlock_conditions_configured = conditions.time_based_unlock_epoch_sec.is_some()
+        || conditions.inactivity_duration_sec.is_some()
+        || conditions.required_heir_approvals.unwrap_or(0) > 0
+        || conditions.required_witness_approvals.unwrap_or(0) > 0
+        || conditions.required_heir_approval_weight.unwrap_or(0) > 0
+        || conditions.required_witness_approval_weight.unwrap_or(0) > 0
+        || conditions.required_death_attestations.unwrap_or(0) > 0
+        || !conditions.depends_on_vaults.is_empty();
+    let required_witnesses = conditions.required_witness_approvals.unwrap_or(0);
+    let required_witness_weight = conditions.required_witness_approval_weight.unwrap_or(0);
+
+    let mut checklist = SetupChecklist {
+        vault_id: config.vault_id,
+        status: config.status,
+        has_content: has_content(&config.vault_id),
+        has_claimed_heir: claimed_heirs > 0,
+        unlock_conditions_configured,
+        witness_required: required_witnesses > 0 || required_witness_weight > 0,
+        has_claimed_witness: claimed_witnesses >= required_witnesses.max(1) && claimed_witness_weight >= required_witness_weight,
+        missing: Vec::new(),
+    };
+    if !checklist.has_content {
+        checklist.missing.push(SetupRequirement::ContentItem);
+    }
+    if !checklist.has_claimed_heir {
+        checklist.missing.push(SetupRequirement::ClaimedHeir);
+    }
+    if !checklist.unlock_conditions_configured {
+        checklist.missing.push(SetupRequirement::UnlockConditions);
+    }
+    if checklist.witness_required && !checklist.has_claimed_witness {
+        checklist.missing.push(SetupRequirement::Witness);
+    }
+    checklist
+}
+
+/// Returns the setup checklist. Owners only.
+pub fn get_setup_checklist(vault_id: &VaultId, caller: PrincipalId) -> Result<SetupChecklist, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.is_owner(&caller) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can view the setup checklist".to_string()));
+    }
+    Ok(evaluate(&config))
+}
+
+/// Moves the vault to `new_status` through `vault_service::apply_status_change` and audits the step.
+fn transition(config: &mut VaultConfig, new_status: VaultStatus, actor: PrincipalId, action: LogAction) -> Result<(), VaultError> {
+    let old_status = config.status;
+    vault_service::apply_status_change(config, new_status, Some(actor))?;
+    storage::audit_logs::record(&config.vault_id, action, actor, format!("Vault status changed from {:?} to {:?}", old_status, new_status));
+    Ok(())
+}
+
+/// Advances the vault through setup as far as its checklist allows. Returns the resulting status.
+fn advance(config: &mut VaultConfig, actor: PrincipalId) -> Result<VaultStatus, VaultError> {
+    if !matches!(config.status, VaultStatus::NeedSetup | VaultStatus::SetupComplete) || config.legal_hold.is_some() {
+        return Ok(config.status);
+    }
+    if !evaluate(config).is_complete() {
+        return Ok(config.status);
+    }
+    if config.status == VaultStatus::NeedSetup {
+        transition(config, VaultStatus::SetupComplete, actor, LogAction::SetupCompleted)?;
+    }
+    transition(config, VaultStatus::Active, actor, LogAction::VaultActivated)?;
+    Ok(config.status)
+}
+
+/// Re-checks a vault's setup after something it depends on changed (content, members, unlock rules).
+/// Errors are logged, never propagated, so callers are not failed by the progression.
+pub(crate) fn advance_setup(vault_id: &VaultId, actor: PrincipalId) {
+    if let Err(e) = storage::vault_configs::require(vault_id).and_then(|mut config| advance(&mut config, actor)) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to check setup of vault {}: {:?}", vault_id, e);
+    }
+}
+
+/// Finalises setup on the owner's request. The owner-side requirements must be met; the vault then
+/// waits in SetupComplete for pending invites and becomes Active once the full checklist is met.
+pub fn finalize_setup(vault_id: &VaultId, caller: PrincipalId) -> Result<VaultStatus, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can finalise setup".to_string()));
+    }
+    config.check_legal_hold()?;
+    match config.status {
+        VaultStatus::NeedSetup => {
+            let checklist = evaluate(&config);
+            if !checklist.owner_ready() {
+                return Err(VaultError::InvalidState(format!("Setup is incomplete, missing: {:?}", checklist.missing)));
+            }
+            transition(&mut config, VaultStatus::SetupComplete, caller, LogAction::SetupCompleted)?;
+            advance(&mut config, caller)
+        }
+        VaultStatus::SetupComplete => advance(&mut config, caller),
+        status => Err(VaultError::InvalidState(format!("Vault is not in setup (status {:?})", status))),
+    }
+}
+
+/// Advances every vault still in setup whose checklist is now met. Called by the scheduler as a
+/// fallback for progressions missed by the event hooks.
+/// NOTE: This iterates the entire config map, which can be inefficient.
+pub fn process_setup_progression(_current_time: Timestamp) -> Result<(), VaultError> {
+    let mut pending: Vec<VaultConfig> = Vec::new();
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        for (_key, value) in map_ref.borrow().iter() {
+            let config: VaultConfig = value.0;
+            if matches!(config.status, VaultStatus::NeedSetup | VaultStatus::SetupComplete) {
+                pending.push(config);
+            }
+        }
+    });
+
+    let system_principal = ic_cdk::id();
+    for mut config in pending {
+        if let Err(e) = advance(&mut config, system_principal) {
+            ic_cdk::eprintln!("❌ ERROR: Failed to advance setup of vault {}: {:?}", config.vault_id, e);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,184 @@
+// src/backend/services/succession_service.rs
+// Backup heirs: each heir may have one designated successor, invited like any member but kept
+// dormant. The successor takes the heir's place (Shamir share, entitlements, content ACLs) when the
+// owner activates it, or automatically once the heir has left an unlock request unanswered too long.
+
+use crate::{
+    error::VaultError,
+    models::{
+        audit_log::LogAction,
+        common::*,
+        notification::NotificationKind,
+        vault_config::{OwnerPermission, VaultConfig},
+        vault_invite_token::{TokenStatus, VaultInviteRequest},
+        vault_member::VaultMember,
+    },
+    services::{invite_service::invite_service, notification_service},
+    storage,
+};
+use candid::Principal;
+use ic_cdk::api::time;
+
+const DAY_NANOS: u64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// The dormant successor designated for `heir`, if any.
+fn dormant_successor(vault_id: &VaultId, heir: &PrincipalId) -> Option<VaultMember> {
+    storage::members::get_members_by_vault(vault_id)
+        .into_iter()
+        .find(|m| m.status == MemberStatus::Dormant && m.successor_of == Some(*heir))
+}
+
+/// Whether an unclaimed successor invite for `heir` is outstanding.
+fn has_pending_successor_invite(vault_id: &VaultId, heir: &PrincipalId) -> bool {
+    storage::tokens::get_tokens_by_vault(vault_id)
+        .iter()
+        .any(|t| t.status == TokenStatus::Issued && t.successor_of == Some(*heir))
+}
+
+/// Invites a successor for a heir. The successor receives a copy of the heir's Shamir share and joins dormant.
+/// Dormant successors can themselves be given a successor, forming a succession chain.
+pub async fn invite_successor(vault_id: &VaultId, heir: PrincipalId, req: VaultInviteRequest, caller: PrincipalId) -> Result<(InviteTokenId, Vec<u8>), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageMembers) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can designate successors".to_string()));
+    }
+    config.check_legal_hold()?;
+    let heir_member = storage::members::get_member(vault_id, &heir)
+        .filter(|m| m.role == Role::Heir && m.status != MemberStatus::Revoked)
+        .ok_or_else(|| VaultError::MemberNotFound(heir.to_string()))?;
+    if dormant_successor(vault_id, &heir).is_some() || has_pending_successor_invite(vault_id, &heir) {
+        return Err(VaultError::InvalidState(format!("Heir {} already has a successor", heir)));
+    }
+
+    let (token_id, share) = invite_service::issue_invite(*vault_id, Role::Heir, caller, req, Some(&heir_member)).await?;
+    storage::audit_logs::record(vault_id, LogAction::SuccessorInvited, caller, format!("Successor invite {} issued for heir {}", token_id, heir));
+    Ok((token_id, share))
+}
+
+/// Puts `successor` in the place of `heir`: the Shamir index (the successor already holds the share
+/// itself, copied at invite time), approval weight and download entitlements,
+/// time capsule recipiency and emergency contact designation move over, and the heir is revoked.
+fn activate(config: &mut VaultConfig, mut heir: VaultMember, mut successor: VaultMember, actor: PrincipalId, reason: &str) {
+    let vault_id = config.vault_id;
+    let current_time = time();
+
+    successor.status = MemberStatus::Active;
+    successor.role = heir.role;
+    successor.shamir_share_index = heir.shamir_share_index;
+    successor.has_approved_unlock = heir.has_approved_unlock;
+    successor.approval_weight = heir.approval_weight;
+    if heir.access_control.custom_limit {
+        successor.access_control.download_limit_per_day = heir.access_control.download_limit_per_day;
+        successor.access_control.custom_limit = true;
+    }
+    successor.updated_at = current_time;
+    storage::members::insert_member(&successor);
+
+    heir.status = MemberStatus::Revoked;
+    heir.has_approved_unlock = false;
+    heir.updated_at = current_time;
+    storage::members::insert_member(&heir);
+
+    // Content addressed to the heir (including trashed items, which may still be restored)
+    let items = storage::content_index::get_index(&vault_id).ok().flatten().unwrap_or_default();
+    for internal_id in items.iter()
+        .filter_map(|id| Principal::from_text(id).ok())
+        .filter_map(storage::content::get_internal_content_id)
+    {
+        let Some(mut item) = storage::content::get_content(internal_id) else { continue };
+        if !item.recipients.contains(&heir.member_id) {
+            continue;
+        }
+        item.recipients.retain(|r| *r != heir.member_id);
+        if !item.recipients.contains(&successor.member_id) {
+            item.recipients.push(successor.member_id);
+        }
+        item.recipients.sort();
+        if let Err(e) = storage::content::update_content(internal_id, item) {
+            ic_cdk::eprintln!("❌ ERROR: Failed to move content recipients of vault {} to successor: {:?}", vault_id, e);
+        }
+    }
+
+    // Emergency contact designation; the heir's own grants are not carried over
+    if !config.emergency_contacts.iter().any(|c| c.member == successor.member_id) {
+        for contact in config.emergency_contacts.iter_mut().filter(|c| c.member == heir.member_id) {
+            contact.member = successor.member_id;
+        }
+    }
+    config.emergency_contacts.retain(|c| c.member != heir.member_id);
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(config);
+
+    ic_cdk::print(format!("🪪 INFO: Successor {} activated in place of heir {} in vault {} ({})", successor.member_id, heir.member_id, vault_id, reason));
+    storage::audit_logs::record(&vault_id, LogAction::SuccessorActivated, actor, format!(
+        "Successor {} activated in place of heir {}: {}", successor.member_id, heir.member_id, reason
+    ));
+
+    let kind = NotificationKind::SuccessorActivated { heir: heir.member_id, successor: successor.member_id };
+    let dedupe_key = format!("successor_activated:{}:{}", vault_id, successor.member_id);
+    notification_service::notify_owner(&vault_id, kind.clone(), dedupe_key.clone());
+    notification_service::notify_principal(&vault_id, successor.member_id, kind.clone(), format!("{}:{}", dedupe_key, successor.member_id));
+    notification_service::notify_principal(&vault_id, heir.member_id, kind, format!("{}:{}", dedupe_key, heir.member_id));
+}
+
+/// Activates the dormant successor of `heir` on the owner's request.
+pub fn activate_successor(vault_id: &VaultId, heir: PrincipalId, caller: PrincipalId) -> Result<VaultMember, VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageMembers) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can activate successors".to_string()));
+    }
+    config.check_legal_hold()?;
+    if matches!(config.status, VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Successors cannot be activated in status {:?}", config.status)));
+    }
+    let heir_member = storage::members::get_member(vault_id, &heir)
+        .filter(|m| m.role == Role::Heir && m.is_participating())
+        .ok_or_else(|| VaultError::MemberNotFound(heir.to_string()))?;
+    let successor = dormant_successor(vault_id, &heir)
+        .ok_or_else(|| VaultError::NotFound(format!("Dormant successor of heir {}", heir)))?;
+
+    let successor_id = successor.member_id;
+    activate(&mut config, heir_member, successor, caller, "activated by owner");
+    storage::members::get_member(vault_id, &successor_id)
+        .ok_or_else(|| VaultError::MemberNotFound(successor_id.to_string()))
+}
+
+/// Activates the successors of heirs who have not approved an unlock request within the vault's
+/// `successor_activation_days`. Only Active and GraceHeir vaults, where approvals are still accepted,
+/// are considered; vaults under legal hold are skipped.
+/// NOTE: This iterates the entire config map, which can be inefficient.
+pub fn process_successor_activations(current_time: Timestamp) -> Result<(), VaultError> {
+    let mut due: Vec<VaultConfig> = Vec::new();
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        for (_key, value) in map_ref.borrow().iter() {
+            let config: VaultConfig = value.0;
+            if config.successor_activation_days == 0 || config.legal_hold.is_some() {
+                continue;
+            }
+            // Heirs can only approve in these states; once unlocked, silence is not a missed request
+            if !matches!(config.status, VaultStatus::Active | VaultStatus::GraceHeir) {
+                continue;
+            }
+            let Some(requested_at) = config.unlock_requested_at else { continue };
+            if current_time >= requested_at.saturating_add(config.successor_activation_days as u64 * DAY_NANOS) {
+                due.push(config);
+            }
+        }
+    });
+
+    let system_principal = ic_cdk::id();
+    for mut config in due {
+        let members = storage::members::get_members_by_vault(&config.vault_id);
+        for successor in members.iter().filter(|m| m.status == MemberStatus::Dormant) {
+            let Some(heir_id) = successor.successor_of else { continue };
+            // Re-read the heir: an earlier activation in this pass may have changed it
+            let Some(heir) = storage::members::get_member(&config.vault_id, &heir_id) else { continue };
+            if heir.role != Role::Heir || !heir.is_participating() || heir.has_approved_unlock {
+                continue;
+            }
+            let reason = format!("heir did not respond to the unlock request within {} days", config.successor_activation_days);
+            activate(&mut config, heir, successor.clone(), system_principal, &reason);
+        }
+    }
+    Ok(())
+}
@@ -3,12 +3,13 @@
 
 use crate::{
     error::VaultError,
-    models::{common::*, vault_config::VaultConfig, vault_content_item::VaultContentItem},
+    models::{common::*, vault_config::{OwnerPermission, VaultConfig}, vault_content_item::VaultContentItem},
     // Use modular storage for content
     storage::{self, Cbor, StorableString, CONTENT_INDEX, /*CONTENT_ITEMS,*/ VAULT_CONFIGS, content as content_storage},
     // Use new principal generator
     utils::crypto::{/* generate_ulid, */ calculate_sha256_hex, generate_unique_principal},
-    services::vault_service,
+    services::{vault_service, activity_service, setup_service},
+    models::activity::ActivityKind,
 };
 use ic_cdk::api::{time, caller as ic_caller}; // Added ic_caller to avoid ambiguity
 use sha2::{Digest, Sha256};
@@ -158,7 +159,7 @@ pub async fn begin_chunked_upload(
 ) -> Result<UploadId, VaultError> { // Return Principal
     // 1. Validate Vault and Permissions
     let vault_config = vault_service::get_vault_config(&vault_id).await?; // Await async call
-    if vault_config.owner != caller { // Simplistic check, might need more roles
+    if !vault_config.has_owner_permission(&caller, OwnerPermission::ManageContent) {
         return Err(VaultError::NotAuthorized("Caller cannot upload to this vault".to_string()));
     }
     // TODO: Add check for vault status allowing uploads (e.g., Active)
@@ -352,21 +353,31 @@ pub async fn finish_chunked_upload(
         payload: full_content,
         payload_size_bytes: state.file_meta.size_bytes,
         payload_sha256: Some(sha256_checksum_hex),
+        release_at: None,
+        recipients: Vec::new(),
+        released_at: None,
+        deleted_at: None,
+        version: 1,
+        updated_by: Some(ic_caller()),
+        history: Vec::new(),
     };
 
     // 8. Store VaultContentItem using the new storage function
     content_storage::insert_content(internal_content_id, item.clone(), content_principal_id)?;
 
-    // 9. Update content index (Needs refactoring based on content storage)
-    // TODO: Update content index logic. It might live within content_storage now
-    // or require the vault_id.
-    /* storage::CONTENT_INDEX.with(|service| {
-        // ... old logic using content_id String ...
-    })?; */
+    // 9. Update content index
+    storage::content_index::add_to_index(&state.vault_id, &content_principal_id)
+        .map_err(|e| VaultError::StorageError(format!("Failed to index content: {}", e)))?;
 
     // 10. Update vault storage usage
     update_vault_storage_usage(&state.vault_id, state.file_meta.size_bytes)?;
 
+    // 11. Content edits by the owner count as proof of life (subject to the vault's activity source)
+    activity_service::note_owner_update(&state.vault_id, ic_caller(), ActivityKind::ContentEdit);
+
+    // 12. The first content item may complete the vault's setup checklist
+    setup_service::advance_setup(&state.vault_id, ic_caller());
+
     ic_cdk::print(format!(
         "✅ INFO: Upload {} finished for vault {}. Content item {} created.",
         upload_id.to_text(), state.vault_id.to_text(), content_principal_id.to_text()
@@ -5,22 +5,43 @@ use crate::{
     error::VaultError,
     models::{
         common::*, // Import common types like VaultId, Timestamp, PrincipalId, VaultStatus
-        vault_config::{VaultConfig,UnlockConditions}, // Import the VaultConfig model
+        vault_config::{VaultConfig, UnlockConditions, ActivitySource, ManagerPermission, OwnerPermission, OwnerRecoveryConfig, SoftDeletion, LegalHold, DependencyState, VaultDependency, DEFAULT_CHALLENGE_WINDOW_DAYS, DEFAULT_MAX_CONTENT_VERSIONS, DEFAULT_RESTORE_WINDOW_DAYS}, // Import the VaultConfig model
+        activity::ActivityKind,
+        attestation::AttestationKind,
+        audit_log::LogAction,
+        notification::NotificationKind,
         vault_member::VaultMember, // Needed for listing vaults by member
         payment::{E8s, PaymentPurpose, PaymentSession, PaymentInitRequest}, // Import Payment related models
         // Add other models as needed, e.g., VaultUpdate payload struct
     },
-    utils::crypto::generate_unique_principal, // Import Principal generation
+    utils::{crypto::generate_unique_principal, guards}, // Import Principal generation
 };
 use crate::storage;
 use ic_cdk::api::{time, caller}; // For timestamps and caller
-use std::time::Duration; // For duration calculations
 use candid::Principal as PrincipalId; // Explicit import
+use std::collections::HashSet;
 use crate::services::payment_service; // Import payment_service
+use crate::services::activity_service;
+use crate::services::notification_service;
+use crate::services::disclosure_service;
+use crate::services::deletion_service;
+use crate::services::setup_service;
+use crate::services::attestation_service;
 
 // Constants for plan calculations
-const TEN_YEARS_IN_NANOS: u64 = 10 * 365 * 24 * 60 * 60 * 1_000_000_000; // Approx 10 years
+const YEAR_IN_NANOS: u64 = 365 * 24 * 60 * 60 * 1_000_000_000; // Approx 1 year
+const DEFAULT_TERM_YEARS: u64 = 10; // Plan prices cover this many years
+const MAX_RENEWAL_YEARS: u32 = 10;
 const E8S_PER_ICP: u64 = 100_000_000;
+const MAX_RESTORE_WINDOW_DAYS: u32 = 90;
+const MAX_CONTENT_VERSIONS: u8 = 20;
+const MAX_LEGAL_HOLD_REASON_LEN: usize = 500;
+const MAX_VAULT_DEPENDENCIES: usize = 5;
+const MAX_SUCCESSOR_ACTIVATION_DAYS: u32 = 365;
+const MAX_APPROVAL_WEIGHT: u32 = 10;
+const MAX_CHALLENGE_WINDOW_DAYS: u32 = 90;
+const MAX_INACTIVITY_WARNINGS: usize = 10;
+const MIN_OWNER_RECOVERY_DELAY_SEC: u64 = 3 * 24 * 60 * 60; // The old principal always gets a veto window
 
 // --- Vault Initialization Struct (Example - Define properly in models or api later) ---
 // This struct would typically come from the API layer (Phase 3)
@@ -41,6 +62,12 @@ pub struct VaultUpdateData {
     pub description: Option<String>,
     pub unlock_conditions: Option<UnlockConditions>,
     pub plan: Option<String>,
+    pub activity_source: Option<ActivitySource>,
+    pub owner_recovery: Option<OwnerRecoveryConfig>,
+    pub restore_window_days: Option<u32>,
+    pub max_content_versions: Option<u8>,
+    pub successor_activation_days: Option<u32>,
+    pub challenge_window_days: Option<u32>,
     // Add fields for updating other settings if needed
 }
 
@@ -77,10 +104,9 @@ fn calculate_prorated_upgrade_cost(
     }
 
     // 2. Calculate remaining time factor
-    // Vault lifetime is 10 years from creation
-    let total_duration_ns = TEN_YEARS_IN_NANOS; 
-    let elapsed_time_ns = current_time_ns.saturating_sub(current_config.created_at);
-    let remaining_time_ns = total_duration_ns.saturating_sub(elapsed_time_ns);
+    // Plan prices cover a full term; the remaining time runs until the (possibly renewed) expiry
+    let total_duration_ns = DEFAULT_TERM_YEARS * YEAR_IN_NANOS;
+    let remaining_time_ns = current_config.expires_at.saturating_sub(current_time_ns);
 
     // Prevent division by zero or negative time
     if remaining_time_ns == 0 || total_duration_ns == 0 {
@@ -109,9 +135,8 @@ pub async fn create_new_vault(init_data: VaultInitData) -> Result<VaultId, Vault
     let vault_id = generate_unique_principal().await?;
     let current_time = time();
 
-    // Calculate expires_at: 10 years from now (as per PRD)
-    let ten_years_duration = Duration::from_secs(10 * 365 * 24 * 60 * 60); // Approx 10 years
-    let expires_at = current_time.saturating_add(ten_years_duration.as_nanos() as u64);
+    // Calculate expires_at: one full term from now (10 years as per PRD)
+    let expires_at = current_time.saturating_add(DEFAULT_TERM_YEARS * YEAR_IN_NANOS);
 
     // Determine storage_quota_bytes based on plan (already implemented)
     let storage_quota_bytes = get_plan_quota_bytes(&init_data.plan)?;
@@ -131,6 +156,27 @@ pub async fn create_new_vault(init_data: VaultInitData) -> Result<VaultId, Vault
         expires_at,
         unlocked_at: None,
         last_accessed_by_owner: Some(current_time), // Owner created it
+        activity_source: ActivitySource::default(),
+        renewal_payer: None,
+        pending_owner_transfer: None,
+        owner_recovery: None,
+        co_owners: Vec::new(),
+        pending_co_owners: Vec::new(),
+        require_joint_approval: false,
+        pending_joint_actions: Vec::new(),
+        emergency_contacts: Vec::new(),
+        disclosure_stages: Vec::new(),
+        unlockable_at: None,
+        post_unlock: Default::default(),
+        receipt_witness: None,
+        restore_window_days: DEFAULT_RESTORE_WINDOW_DAYS,
+        soft_deleted: None,
+        legal_hold: None,
+        max_content_versions: DEFAULT_MAX_CONTENT_VERSIONS,
+        successor_activation_days: 0,
+        unlock_requested_at: None,
+        challenge_window_days: DEFAULT_CHALLENGE_WINDOW_DAYS,
+        unlock_challenge: None,
     };
 
     // Store the configuration using the dedicated storage helper function
@@ -148,14 +194,51 @@ pub async fn create_new_vault(init_data: VaultInitData) -> Result<VaultId, Vault
 /// # Returns
 /// * `Result<VaultConfig, VaultError>` - The vault configuration or an error if not found.
 pub async fn get_vault_config(vault_id: &VaultId) -> Result<VaultConfig, VaultError> {
-    // Use the helper function which handles key conversion
-    storage::vault_configs::get_vault_config(vault_id)
-        .ok_or_else(|| VaultError::VaultNotFound(vault_id.clone().to_string()))
+    storage::vault_configs::require(vault_id)
+}
+
+/// True if unlock rules, plan changes and deletion must go through a joint action.
+pub fn joint_approval_required(config: &VaultConfig) -> bool {
+    config.require_joint_approval && !config.co_owners.is_empty()
+}
+
+/// Updates a vault's name and description. Owners with the settings permission and managers;
+/// unlike `update_vault_config`, it cannot touch unlock rules, the plan or any other setting.
+pub fn update_vault_details(vault_id: &VaultId, name: Option<String>, description: Option<String>, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !guards::has_delegated_permission(&config, &caller, ManagerPermission::EditDetails) {
+        return Err(VaultError::NotAuthorized("Only the vault owner or a manager can edit vault details".to_string()));
+    }
+    if config.status == VaultStatus::Deleted {
+        return Err(VaultError::InvalidState("Cannot edit a deleted vault".to_string()));
+    }
+
+    let mut changed = false;
+    if let Some(name) = name.map(|n| n.trim().to_string()) {
+        if name.is_empty() {
+            return Err(VaultError::InvalidInput("Vault name cannot be empty".to_string()));
+        }
+        if config.name != name {
+            config.name = name;
+            changed = true;
+        }
+    }
+    if description != config.description {
+        config.description = description;
+        changed = true;
+    }
+    if changed {
+        config.updated_at = time();
+        storage::vault_configs::insert_vault_config(&config);
+        storage::audit_logs::record(vault_id, LogAction::VaultUpdated, caller, "Vault name or description updated".to_string());
+    }
+    Ok(())
 }
 
 /// Updates an existing vault's configuration.
 /// If a plan upgrade requires payment, initiates a payment session and returns it.
 /// If it's a downgrade or non-plan update, applies changes directly.
+/// On jointly approved vaults, unlock rule and plan changes must be proposed as joint actions.
 ///
 /// # Arguments
 /// * `vault_id` - The ID of the vault to update.
@@ -168,13 +251,31 @@ pub async fn update_vault_config(
     vault_id: &VaultId,
     update_data: VaultUpdateData,
     caller: PrincipalId,
+) -> Result<Option<PaymentSession>, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+
+    if joint_approval_required(&config) {
+        let changes_unlock_rules = update_data.unlock_conditions.as_ref().map_or(false, |u| *u != config.unlock_conditions);
+        let changes_plan = update_data.plan.as_ref().map_or(false, |p| *p != config.plan);
+        if changes_unlock_rules || changes_plan {
+            return Err(VaultError::JointApprovalRequired("Unlock rules and plan changes must be proposed as a joint action".to_string()));
+        }
+    }
+
+    apply_vault_update(vault_id, update_data, caller).await
+}
+
+/// Applies a vault update without the joint approval check (used once a joint action is approved).
+pub(crate) async fn apply_vault_update(
+    vault_id: &VaultId,
+    update_data: VaultUpdateData,
+    caller: PrincipalId,
 ) -> Result<Option<PaymentSession>, VaultError> {
     // 1. Retrieve existing config using the helper
-    let mut config = storage::vault_configs::get_vault_config(vault_id)
-        .ok_or_else(|| VaultError::VaultNotFound(vault_id.clone().to_string()))?;
+    let mut config = storage::vault_configs::require(vault_id)?;
 
-    // 2. Authorization Check: Ensure caller is the owner
-    if config.owner != caller {
+    // 2. Authorization Check: Ensure caller is an owner allowed to change settings
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
         return Err(VaultError::NotAuthorized(format!(
             "Caller {} is not the owner of vault {}",
             caller, vault_id
@@ -198,10 +299,77 @@ pub async fn update_vault_config(
     }
     if let Some(unlock_conditions) = update_data.unlock_conditions {
         if config.unlock_conditions != unlock_conditions {
+            if config.unlock_conditions.depends_on_vaults != unlock_conditions.depends_on_vaults {
+                validate_vault_dependencies(&config, &unlock_conditions.depends_on_vaults)?;
+            }
+            validate_approval_weights(vault_id, &unlock_conditions, None)?;
+            validate_inactivity_warnings(&unlock_conditions)?;
             config.unlock_conditions = unlock_conditions;
             needs_save = true;
         }
     }
+    if let Some(activity_source) = update_data.activity_source {
+        if config.activity_source != activity_source {
+            config.activity_source = activity_source;
+            needs_save = true;
+        }
+    }
+    if let Some(restore_window_days) = update_data.restore_window_days {
+        if restore_window_days > MAX_RESTORE_WINDOW_DAYS {
+            return Err(VaultError::InvalidInput(format!("Restore window cannot exceed {} days", MAX_RESTORE_WINDOW_DAYS)));
+        }
+        if config.restore_window_days != restore_window_days {
+            config.restore_window_days = restore_window_days;
+            needs_save = true;
+        }
+    }
+    if let Some(max_content_versions) = update_data.max_content_versions {
+        if max_content_versions > MAX_CONTENT_VERSIONS {
+            return Err(VaultError::InvalidInput(format!("At most {} versions can be retained per item", MAX_CONTENT_VERSIONS)));
+        }
+        // A lower cap takes effect on each item's next edit
+        if config.max_content_versions != max_content_versions {
+            config.max_content_versions = max_content_versions;
+            needs_save = true;
+        }
+    }
+    if let Some(successor_activation_days) = update_data.successor_activation_days {
+        if successor_activation_days > MAX_SUCCESSOR_ACTIVATION_DAYS {
+            return Err(VaultError::InvalidInput(format!("Successor activation cannot exceed {} days", MAX_SUCCESSOR_ACTIVATION_DAYS)));
+        }
+        if config.successor_activation_days != successor_activation_days {
+            config.successor_activation_days = successor_activation_days;
+            needs_save = true;
+        }
+    }
+    if let Some(challenge_window_days) = update_data.challenge_window_days {
+        if challenge_window_days > MAX_CHALLENGE_WINDOW_DAYS {
+            return Err(VaultError::InvalidInput(format!("Challenge window cannot exceed {} days", MAX_CHALLENGE_WINDOW_DAYS)));
+        }
+        if config.challenge_window_days != challenge_window_days {
+            config.challenge_window_days = challenge_window_days;
+            needs_save = true;
+        }
+    }
+    if let Some(owner_recovery) = update_data.owner_recovery {
+        if owner_recovery.required_heir_attestations == 0 && owner_recovery.required_witness_attestations == 0 {
+            return Err(VaultError::InvalidInput("Owner recovery requires at least one attestation".to_string()));
+        }
+        if owner_recovery.delay_sec < MIN_OWNER_RECOVERY_DELAY_SEC {
+            return Err(VaultError::InvalidInput(format!("Owner recovery delay must be at least {} seconds", MIN_OWNER_RECOVERY_DELAY_SEC)));
+        }
+        let members = storage::members::get_members_by_vault(vault_id);
+        let participating = |role: Role| members.iter().filter(|m| m.role == role && m.is_participating()).count() as u32;
+        if owner_recovery.required_heir_attestations > participating(Role::Heir)
+            || owner_recovery.required_witness_attestations > participating(Role::Witness)
+        {
+            return Err(VaultError::InvalidInput("Owner recovery needs more attestations than the vault has heirs or witnesses".to_string()));
+        }
+        if config.owner_recovery.as_ref() != Some(&owner_recovery) {
+            config.owner_recovery = Some(owner_recovery);
+            needs_save = true;
+        }
+    }
 
     // --- Handle Plan Change ---
     if let Some(new_plan) = update_data.plan {
@@ -268,6 +436,14 @@ pub async fn update_vault_config(
         }
     }
 
+    // Any owner update call counts as proof of life (subject to the vault's activity source)
+    activity_service::note_owner_update(vault_id, caller, ActivityKind::VaultUpdate);
+
+    // New unlock rules may complete the vault's setup checklist
+    if needs_save {
+        setup_service::advance_setup(vault_id, caller);
+    }
+
     // Return the payment session if one was created, otherwise None
     Ok(payment_session_needed)
 }
@@ -294,8 +470,7 @@ pub async fn finalize_plan_change(vault_id: &VaultId, new_plan: String) -> Resul
         "INFO: Finalizing plan change for vault {} to {}",
         vault_id, new_plan
     ));
-    let mut config = storage::vault_configs::get_vault_config(vault_id)
-        .ok_or_else(|| VaultError::VaultNotFound(vault_id.clone().to_string()))?;
+    let mut config = storage::vault_configs::require(vault_id)?;
 
     // Get new quota
     let new_storage_quota_bytes = get_plan_quota_bytes(&new_plan)?;
@@ -335,6 +510,82 @@ pub async fn finalize_plan_change(vault_id: &VaultId, new_plan: String) -> Resul
     }
 }
 
+/// Quotes the price of extending a vault's term by `years`, at its current plan's yearly rate.
+pub fn quote_renewal(vault_id: &VaultId, years: u32) -> Result<E8s, VaultError> {
+    if years == 0 || years > MAX_RENEWAL_YEARS {
+        return Err(VaultError::InvalidInput(format!("Renewal must be between 1 and {} years", MAX_RENEWAL_YEARS)));
+    }
+    let config = storage::vault_configs::require(vault_id)?;
+    let term_price_e8s = get_plan_base_price_e8s(&config.plan)?;
+    Ok((term_price_e8s as u128 * years as u128 / DEFAULT_TERM_YEARS as u128) as u64)
+}
+
+/// Starts a renewal payment for a vault. Allowed for owners with settings permission, managers
+/// (`ManagerPermission::RenewPlan`) and the designated renewal payer.
+///
+/// # Returns
+/// * `Result<PaymentSession, VaultError>` - The session to pay; the term is extended once it is verified.
+pub async fn start_renewal(vault_id: &VaultId, years: u32, caller: PrincipalId) -> Result<PaymentSession, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if config.renewal_payer != Some(caller) && !guards::has_delegated_permission(&config, &caller, ManagerPermission::RenewPlan) {
+        return Err(VaultError::NotAuthorized("Only the owner, a manager or the designated payer can renew this vault".to_string()));
+    }
+    config.check_legal_hold()?; // Refuse before any payment is taken
+    if !matches!(
+        config.status,
+        VaultStatus::NeedSetup | VaultStatus::SetupComplete | VaultStatus::Active | VaultStatus::GraceMaster | VaultStatus::GraceHeir
+    ) {
+        return Err(VaultError::InvalidState(format!("Cannot renew a vault in status {:?}", config.status)));
+    }
+
+    let amount_e8s = quote_renewal(vault_id, years)?;
+    let payment_req = PaymentInitRequest {
+        vault_plan: config.plan.clone(),
+        amount_e8s,
+    };
+    ic_cdk::print(format!(
+        "INFO: Renewal of vault {} for {} year(s) requested by {} ({} e8s).",
+        vault_id, years, caller, amount_e8s
+    ));
+    payment_service::initialize_payment_session(payment_req, caller, Some(PaymentPurpose::VaultRenewal { vault_id: *vault_id, years })).await
+}
+
+/// Internal function to extend a vault's term after a renewal payment is verified.
+/// The term is extended from the current expiry, and vaults in a grace period become Active again.
+/// Should only be called by the payment service.
+pub async fn finalize_renewal(vault_id: &VaultId, years: u32) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+
+    config.expires_at = config.expires_at.saturating_add(years as u64 * YEAR_IN_NANOS);
+    config.updated_at = time();
+    // The extension and the status change are stored together. A hold placed after the payment
+    // started keeps the grace status; the vault becomes Active when the hold is released.
+    if matches!(config.status, VaultStatus::GraceMaster | VaultStatus::GraceHeir) && config.legal_hold.is_none() {
+        apply_status_change(&mut config, VaultStatus::Active, None)?;
+    } else {
+        storage::vault_configs::insert_vault_config(&config);
+    }
+    ic_cdk::print(format!(
+        "✅ SUCCESS: Vault {} renewed for {} year(s), now expires at {}.",
+        vault_id, years, config.expires_at
+    ));
+    Ok(())
+}
+
+/// Designates (or clears) a principal allowed to pay for renewals besides the owner.
+pub fn set_renewal_payer(vault_id: &VaultId, payer: Option<PrincipalId>, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can designate a renewal payer".to_string()));
+    }
+    config.renewal_payer = payer;
+    config.updated_at = time();
+    storage::vault_configs::insert_vault_config(&config);
+
+    storage::audit_logs::record(vault_id, LogAction::RenewalPayerChanged, caller, format!("Renewal payer set to {:?}", payer));
+    Ok(())
+}
+
 /// Saves the provided VaultConfig to stable storage.
 /// This is intended for internal use by services after modifying config.
 ///
@@ -362,21 +613,35 @@ pub async fn save_vault_config(config: &VaultConfig) -> Result<(), VaultError> {
 /// * `Result<(), VaultError>` - Success or an error if the transition is invalid or the vault is not found.
 pub async fn set_vault_status(vault_id: &VaultId, new_status: VaultStatus, triggering_principal: Option<PrincipalId>) -> Result<(), VaultError> {
     // 1. Retrieve existing config using the helper
-    let mut config = storage::vault_configs::get_vault_config(vault_id)
-        .ok_or_else(|| VaultError::VaultNotFound(vault_id.clone().to_string()))?;
+    let mut config = storage::vault_configs::require(vault_id)?;
+
+    apply_status_change(&mut config, new_status, triggering_principal)
+}
 
+/// Validates and applies a status change to an already loaded config, then persists it.
+/// `config` reflects the stored state afterwards. Synchronous core of `set_vault_status`.
+pub(crate) fn apply_status_change(config: &mut VaultConfig, new_status: VaultStatus, triggering_principal: Option<PrincipalId>) -> Result<(), VaultError> {
+    let vault_id = config.vault_id;
     let old_status = config.status;
 
+    // Held vaults are frozen in their current state
+    if old_status != new_status {
+        config.check_legal_hold()?;
+    }
+
     // --- State Transition Validation (Based on plans/readme.md Lifecycle) ---
     let is_valid_transition = match (old_status, new_status) {
         // Initial Setup Flow
         (VaultStatus::Draft, VaultStatus::NeedSetup) => true, // After payment verification
         (VaultStatus::NeedSetup, VaultStatus::SetupComplete) => true, // After owner finishes setup (config + invite sent)
         (VaultStatus::SetupComplete, VaultStatus::Active) => true, // After >= 1 heir joined
+        (VaultStatus::NeedSetup, VaultStatus::Unlockable) => true, // Recovery QR redeemed
+        (VaultStatus::SetupComplete, VaultStatus::Unlockable) => true, // Recovery QR redeemed
 
         // Active State Transitions
         (VaultStatus::Active, VaultStatus::Active) => true, // Allow updates while active
         (VaultStatus::Active, VaultStatus::GraceMaster) => true, // Plan expires
+        (VaultStatus::Active, VaultStatus::Unlockable) => true, // Conditions met or recovery QR redeemed
 
         // Grace Master Flow
         (VaultStatus::GraceMaster, VaultStatus::Active) => true, // Plan renewed
@@ -418,6 +683,23 @@ pub async fn set_vault_status(vault_id: &VaultId, new_status: VaultStatus, trigg
         config.status = new_status;
         config.updated_at = time(); // Update timestamp on status change
 
+        // Disclosure stage delays count from the moment the vault becomes unlockable
+        let mut released_stages = Vec::new();
+        if new_status == VaultStatus::Unlockable {
+            let unlockable_at = config.updated_at;
+            config.unlockable_at = Some(unlockable_at);
+            released_stages = disclosure_service::advance_disclosure_stages(config, unlockable_at);
+        }
+
+        // A pending ownership transfer cannot outlive the vault's transferable states
+        if matches!(new_status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+            if let Some(transfer) = config.pending_owner_transfer.take() {
+                storage::audit_logs::record(&vault_id, LogAction::OwnerTransferCancelled, ic_cdk::api::id(), format!(
+                    "Ownership transfer to {} cancelled: vault became {:?}", transfer.new_owner, new_status
+                ));
+            }
+        }
+
         // Reinstate logic for unlocked_at
         if new_status == VaultStatus::Unlocked {
             config.unlocked_at = Some(time());
@@ -427,11 +709,23 @@ pub async fn set_vault_status(vault_id: &VaultId, new_status: VaultStatus, trigg
              config.unlocked_at = None;
         }
 
+        // A vault leaving setup starts counting as active
+        if new_status == VaultStatus::Active && matches!(old_status, VaultStatus::NeedSetup | VaultStatus::SetupComplete) {
+            if let Err(e) = storage::metrics::update_active_vault_count(1) {
+                ic_cdk::eprintln!("❌ ERROR: Failed incrementing active vault metric: {:?}", e);
+            }
+        }
+
         // Insert the updated config back using the helper function
-        match storage::vault_configs::insert_vault_config(&config) {
+        match storage::vault_configs::insert_vault_config(config) {
             Some(_) => {
                  let principal_str = triggering_principal.map_or_else(|| "System".to_string(), |p| p.to_string());
                  ic_cdk::print(format!("📝 INFO: Vault {} status changed from {:?} to {:?} by {}", vault_id, old_status, new_status, principal_str));
+                 notify_status_change(config);
+                 disclosure_service::announce_releases(config, &released_stages);
+                 if matches!(new_status, VaultStatus::Unlockable | VaultStatus::Unlocked) {
+                     ic_cdk::spawn(unlock_dependents(vault_id));
+                 }
                  Ok(())
              },
              None => {
@@ -445,6 +739,20 @@ pub async fn set_vault_status(vault_id: &VaultId, new_status: VaultStatus, trigg
     }
 }
 
+/// Enqueues the notifications that accompany a status change.
+fn notify_status_change(config: &VaultConfig) {
+    let dedupe_key = format!("grace_started:{}:{:?}:{}", config.vault_id, config.status, config.expires_at);
+    let kind = NotificationKind::GracePeriodStarted { status: config.status };
+    match config.status {
+        VaultStatus::GraceMaster => notification_service::notify_owner(&config.vault_id, kind, dedupe_key),
+        VaultStatus::GraceHeir => {
+            notification_service::notify_owner(&config.vault_id, kind.clone(), dedupe_key.clone());
+            notification_service::notify_heirs(&config.vault_id, kind, &dedupe_key);
+        }
+        _ => {}
+    }
+}
+
 /// Trigger vault unlock process (e.g., called by witness or scheduler).
 /// Checks unlock conditions and transitions state to Unlockable if met.
 ///
@@ -455,7 +763,7 @@ pub async fn set_vault_status(vault_id: &VaultId, new_status: VaultStatus, trigg
 /// # Returns
 /// * `Result<(), VaultError>` - Success or an error.
 pub async fn trigger_unlock(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
-    let config = get_vault_config(vault_id).await?;
+    let mut config = get_vault_config(vault_id).await?;
 
     // Authorization: Check if caller is a witness or admin (add roles later)
     let is_authorized = storage::members::is_member_with_role(vault_id, &caller, Role::Witness).await?
@@ -465,6 +773,14 @@ pub async fn trigger_unlock(vault_id: &VaultId, caller: PrincipalId) -> Result<(
          return Err(VaultError::NotAuthorized("Only a witness or admin can trigger unlock".to_string()));
     }
 
+    // Unlock cannot be executed while the vault is under legal hold
+    config.check_legal_hold()?;
+
+    // Let the owner and heirs know someone asked for the vault to be opened (at most once a day per requester)
+    let dedupe_key = format!("unlock_requested:{}:{}:{}", vault_id, caller, time() / (24 * 60 * 60 * 1_000_000_000));
+    notification_service::notify_owner(vault_id, NotificationKind::UnlockRequested { requested_by: caller }, dedupe_key.clone());
+    notification_service::notify_heirs(vault_id, NotificationKind::UnlockRequested { requested_by: caller }, &dedupe_key);
+
     // Check if vault is in a state where unlock can be triggered (GraceHeir as per diagram, or Active if conditions met early?)
     // Let's allow trigger from Active or GraceHeir, check_unlock_conditions will validate timing/inactivity etc.
     if !matches!(config.status, VaultStatus::Active | VaultStatus::GraceHeir) {
@@ -474,6 +790,12 @@ pub async fn trigger_unlock(vault_id: &VaultId, caller: PrincipalId) -> Result<(
         )));
     }
 
+    // Heirs that leave the request unanswered may be replaced by their successors (see succession_service)
+    if config.unlock_requested_at.is_none() {
+        config.unlock_requested_at = Some(time());
+        storage::vault_configs::insert_vault_config(&config);
+    }
+
     // Check unlock conditions (time, inactivity, approvals)
     let conditions_met = check_unlock_conditions(&config).await?;
 
@@ -487,9 +809,238 @@ pub async fn trigger_unlock(vault_id: &VaultId, caller: PrincipalId) -> Result<(
     }
 }
 
+/// Records a heir's or witness's approval of the vault's unlock, then makes the vault
+/// Unlockable if its conditions are now met. Each member approves once.
+///
+/// # Returns
+/// * `Result<VaultStatus, VaultError>` - The vault status after the approval.
+pub async fn approve_unlock(vault_id: &VaultId, caller: PrincipalId) -> Result<VaultStatus, VaultError> {
+    let config = get_vault_config(vault_id).await?;
+    let mut member = storage::members::get_member(vault_id, &caller)
+        .filter(|m| matches!(m.role, Role::Heir | Role::Witness) && m.is_participating())
+        .ok_or_else(|| VaultError::NotAuthorized("Only heirs and witnesses can approve an unlock".to_string()))?;
+    config.check_legal_hold()?;
+    if !matches!(config.status, VaultStatus::Active | VaultStatus::GraceHeir) {
+        return Err(VaultError::InvalidState(format!("Cannot approve unlock in status {:?}", config.status)));
+    }
+    if member.has_approved_unlock {
+        return Err(VaultError::InvalidState("Unlock already approved by this member".to_string()));
+    }
+
+    member.has_approved_unlock = true;
+    member.updated_at = time();
+    storage::members::insert_member(&member);
+    storage::approvals::record_approval(vault_id, member.role).await?;
+    storage::audit_logs::record(vault_id, LogAction::MemberApprovedUnlock, caller, format!(
+        "{:?} approved unlock with weight {}", member.role, member.approval_weight
+    ));
+
+    if check_unlock_conditions(&config).await? {
+        set_vault_status(vault_id, VaultStatus::Unlockable, Some(caller)).await?;
+        return Ok(VaultStatus::Unlockable);
+    }
+    Ok(config.status)
+}
+
+/// Sum of approval weights of the participating members holding `role` that approved the unlock.
+fn approved_weight(vault_id: &VaultId, role: Role) -> u32 {
+    storage::members::get_members_by_vault(vault_id)
+        .iter()
+        .filter(|m| m.role == role && m.is_participating() && m.has_approved_unlock)
+        .fold(0u32, |sum, m| sum.saturating_add(m.approval_weight))
+}
+
+/// The most approval weight `role` can bring: participating members' weights, with `weight_override`
+/// applied (weight 0 leaves the member out). Pending invites are not counted since they can expire,
+/// and dormant successors only ever take over their heir's weight.
+fn reachable_weight(vault_id: &VaultId, role: Role, weight_override: Option<(PrincipalId, u32)>) -> u32 {
+    storage::members::get_members_by_vault(vault_id)
+        .iter()
+        .filter(|m| m.role == role && m.is_participating())
+        .map(|m| match weight_override {
+            Some((member, weight)) if member == m.member_id => weight,
+            _ => m.approval_weight,
+        })
+        .fold(0u32, |sum, weight| sum.saturating_add(weight))
+}
+
+/// Checks that the weighted approval thresholds of `conditions` can be reached by the vault's roster.
+fn validate_approval_weights(vault_id: &VaultId, conditions: &UnlockConditions, weight_override: Option<(PrincipalId, u32)>) -> Result<(), VaultError> {
+    let thresholds = [
+        (Role::Heir, conditions.required_heir_approval_weight.unwrap_or(0)),
+        (Role::Witness, conditions.required_witness_approval_weight.unwrap_or(0)),
+    ];
+    for (role, required) in thresholds {
+        if required == 0 {
+            continue;
+        }
+        let reachable = reachable_weight(vault_id, role, weight_override);
+        if required > reachable {
+            return Err(VaultError::InvalidInput(format!(
+                "Required {:?} approval weight {} exceeds the {} the vault's {:?} members can reach", role, required, reachable, role
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the owner reminder schedule: at most `MAX_INACTIVITY_WARNINGS` distinct, non-zero day
+/// counts, each falling inside the inactivity window.
+fn validate_inactivity_warnings(conditions: &UnlockConditions) -> Result<(), VaultError> {
+    let Some(days) = conditions.inactivity_warning_days.as_ref() else { return Ok(()) };
+    let window_sec = conditions.inactivity_duration_sec
+        .ok_or_else(|| VaultError::InvalidInput("Inactivity warnings require an inactivity duration".to_string()))?;
+    if days.len() > MAX_INACTIVITY_WARNINGS {
+        return Err(VaultError::InvalidInput(format!("At most {} inactivity warnings can be scheduled", MAX_INACTIVITY_WARNINGS)));
+    }
+    let mut seen = std::collections::HashSet::new();
+    for &day in days {
+        if day == 0 || !seen.insert(day) {
+            return Err(VaultError::InvalidInput("Inactivity warning days must be non-zero and unique".to_string()));
+        }
+        if day as u64 * 24 * 60 * 60 >= window_sec {
+            return Err(VaultError::InvalidInput(format!("Inactivity warning at {} day(s) falls outside the inactivity window", day)));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the vault's weighted approval thresholds stay reachable once `member` leaves the
+/// roster. Called by every path that removes a member.
+pub(crate) fn check_member_removal(vault_id: &VaultId, member: PrincipalId) -> Result<(), VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    validate_approval_weights(vault_id, &config.unlock_conditions, Some((member, 0)))
+}
+
+/// Sets how much a heir's or witness's unlock approval counts. On jointly approved vaults this
+/// must be proposed as a joint action.
+pub fn set_member_approval_weight(vault_id: &VaultId, member: PrincipalId, weight: u32, caller: PrincipalId) -> Result<VaultMember, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if joint_approval_required(&config) {
+        return Err(VaultError::JointApprovalRequired("Approval weights must be proposed as a joint action".to_string()));
+    }
+    apply_member_approval_weight(vault_id, member, weight, caller)
+}
+
+/// Sets a member's approval weight without the joint approval check (used once a joint action is approved).
+pub(crate) fn apply_member_approval_weight(vault_id: &VaultId, member: PrincipalId, weight: u32, caller: PrincipalId) -> Result<VaultMember, VaultError> {
+    let config = storage::vault_configs::require(vault_id)?;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) {
+        return Err(VaultError::NotAuthorized("Only the vault owner can change approval weights".to_string()));
+    }
+    if matches!(config.status, VaultStatus::Unlockable | VaultStatus::Unlocked | VaultStatus::Expired | VaultStatus::Deleted) {
+        return Err(VaultError::InvalidState(format!("Cannot change approval weights in status {:?}", config.status)));
+    }
+    if weight == 0 || weight > MAX_APPROVAL_WEIGHT {
+        return Err(VaultError::InvalidInput(format!("Approval weight must be between 1 and {}", MAX_APPROVAL_WEIGHT)));
+    }
+    let mut target = storage::members::get_member(vault_id, &member)
+        .filter(|m| matches!(m.role, Role::Heir | Role::Witness) && m.status != MemberStatus::Revoked)
+        .ok_or_else(|| VaultError::MemberNotFound(member.to_string()))?;
+    if target.status == MemberStatus::Dormant {
+        return Err(VaultError::InvalidState("Successors take over their heir's weight when activated".to_string()));
+    }
+    // Lowering a weight must not leave a weighted threshold out of reach
+    validate_approval_weights(vault_id, &config.unlock_conditions, Some((member, weight)))?;
+
+    let old_weight = target.approval_weight;
+    target.approval_weight = weight;
+    target.updated_at = time();
+    storage::members::insert_member(&target);
+    storage::audit_logs::record(vault_id, LogAction::ApprovalWeightChanged, caller, format!(
+        "Approval weight of {:?} {} changed from {} to {}", target.role, member, old_weight, weight
+    ));
+    Ok(target)
+}
+
+/// The first linked vault that has not reached its required state, if any.
+/// A linked vault that no longer exists never satisfies its dependency.
+fn unmet_dependency(config: &VaultConfig) -> Option<&VaultDependency> {
+    config.unlock_conditions.depends_on_vaults.iter().find(|dependency| {
+        storage::vault_configs::get_vault_config(&dependency.vault_id)
+            .map_or(true, |linked| !dependency.is_met_by(linked.status))
+    })
+}
+
+/// Checks a new set of linked vaults: each must exist and be owned (or co-owned) by this vault's
+/// owner, and following the links must never lead back to this vault.
+fn validate_vault_dependencies(config: &VaultConfig, dependencies: &[VaultDependency]) -> Result<(), VaultError> {
+    if dependencies.len() > MAX_VAULT_DEPENDENCIES {
+        return Err(VaultError::InvalidInput(format!("A vault can depend on at most {} other vaults", MAX_VAULT_DEPENDENCIES)));
+    }
+    let mut seen = HashSet::new();
+    for dependency in dependencies {
+        if dependency.vault_id == config.vault_id {
+            return Err(VaultError::InvalidInput("A vault cannot depend on itself".to_string()));
+        }
+        if !seen.insert(dependency.vault_id) {
+            return Err(VaultError::InvalidInput(format!("Vault {} is listed more than once", dependency.vault_id)));
+        }
+        if dependency.required_state == DependencyState::Unlocked {
+            // Nothing moves a vault from Unlockable to Unlocked yet, so this could never be met
+            return Err(VaultError::InvalidInput("Dependencies can only require a vault to be Unlockable".to_string()));
+        }
+        let linked = storage::vault_configs::get_vault_config(&dependency.vault_id)
+            .filter(|linked| linked.status != VaultStatus::Deleted)
+            .ok_or_else(|| VaultError::VaultNotFound(dependency.vault_id.to_string()))?;
+        if !linked.is_owner(&config.owner) {
+            return Err(VaultError::NotAuthorized(format!(
+                "Vault {} can only depend on vaults its owner controls", config.vault_id
+            )));
+        }
+    }
+
+    // Depth-first walk over the stored links, starting from the new ones
+    let mut stack: Vec<VaultId> = dependencies.iter().map(|d| d.vault_id).collect();
+    let mut visited = HashSet::new();
+    while let Some(current) = stack.pop() {
+        if current == config.vault_id {
+            return Err(VaultError::InvalidInput("Vault dependencies would form a cycle".to_string()));
+        }
+        if !visited.insert(current) {
+            continue;
+        }
+        if let Some(linked) = storage::vault_configs::get_vault_config(&current) {
+            stack.extend(linked.unlock_conditions.depends_on_vaults.iter().map(|d| d.vault_id));
+        }
+    }
+    Ok(())
+}
+
+/// Re-evaluates the vaults linked to `vault_id` once it opens, and makes those whose
+/// conditions are now met Unlockable. Runs as its own task so chains of links unlock in turn.
+/// NOTE: This iterates the entire config map, which can be inefficient.
+async fn unlock_dependents(vault_id: VaultId) {
+    let mut dependents: Vec<VaultConfig> = Vec::new();
+    storage::vault_configs::CONFIGS.with(|map_ref| {
+        for (_key, value) in map_ref.borrow().iter() {
+            let config: VaultConfig = value.0;
+            if matches!(config.status, VaultStatus::Active | VaultStatus::GraceHeir)
+                && config.legal_hold.is_none()
+                && config.unlock_conditions.depends_on_vaults.iter().any(|d| d.vault_id == vault_id)
+            {
+                dependents.push(config);
+            }
+        }
+    });
+
+    for config in dependents {
+        match check_unlock_conditions(&config).await {
+            Ok(true) => {
+                if let Err(e) = set_vault_status(&config.vault_id, VaultStatus::Unlockable, None).await {
+                    ic_cdk::eprintln!("❌ ERROR: Failed to unlock linked vault {}: {:?}", config.vault_id, e);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed to check unlock conditions of linked vault {}: {:?}", config.vault_id, e),
+        }
+    }
+}
+
 /// Checks if unlock conditions are met for a vault.
-/// Returns true if *any* of the configured conditions are satisfied.
-async fn check_unlock_conditions(config: &VaultConfig) -> Result<bool, VaultError> {
+/// Returns true if *any* of the configured conditions are satisfied, once every linked vault
+/// has reached its required state.
+pub(crate) async fn check_unlock_conditions(config: &VaultConfig) -> Result<bool, VaultError> {
     let current_time_ns = time(); // Use nanoseconds for internal checks
     let conditions = &config.unlock_conditions;
     let vault_id = &config.vault_id;
@@ -497,6 +1048,28 @@ async fn check_unlock_conditions(config: &VaultConfig) -> Result<bool, VaultErro
     ic_cdk::print(format!("🔍 UNLOCK CHECK: Vault {}. Current time: {}", vault_id, current_time_ns));
     ic_cdk::print(format!("🔍 UNLOCK CHECK: Conditions: {:?}", conditions));
 
+    // 0. Linked vaults gate every other condition
+    if !conditions.depends_on_vaults.is_empty() {
+        if let Some(dependency) = unmet_dependency(config) {
+            ic_cdk::print(format!(
+                "⏳ UNLOCK CHECK: Vault {} waits for linked vault {} to be {:?}.",
+                vault_id, dependency.vault_id, dependency.required_state
+            ));
+            return Ok(false);
+        }
+        let has_own_conditions = conditions.time_based_unlock_epoch_sec.is_some()
+            || conditions.inactivity_duration_sec.is_some()
+            || conditions.required_heir_approvals.unwrap_or(0) > 0
+            || conditions.required_witness_approvals.unwrap_or(0) > 0
+            || conditions.required_heir_approval_weight.unwrap_or(0) > 0
+            || conditions.required_witness_approval_weight.unwrap_or(0) > 0
+            || conditions.required_death_attestations.unwrap_or(0) > 0;
+        if !has_own_conditions {
+            ic_cdk::print(format!("✅ UNLOCK CHECK: Vault {} passed linked vault condition.", vault_id));
+            return Ok(true);
+        }
+    }
+
     // 1. Time-based unlock check
     if let Some(unlock_time_sec) = conditions.time_based_unlock_epoch_sec {
         // Convert unlock_time_sec (epoch seconds) to nanoseconds
@@ -513,7 +1086,7 @@ async fn check_unlock_conditions(config: &VaultConfig) -> Result<bool, VaultErro
 
     // 2. Inactivity-based unlock check
     if let Some(inactivity_sec) = conditions.inactivity_duration_sec {
-        let last_active_time_ns = config.last_accessed_by_owner.unwrap_or(config.created_at); // Use created_at if never accessed
+        let last_active_time_ns = config.owner_activity_reference(); // Combines co-owners per inactivity_mode
         let inactivity_duration_ns = current_time_ns.saturating_sub(last_active_time_ns);
         let required_inactivity_nanos = (inactivity_sec as u128) * 1_000_000_000;
         ic_cdk::print(format!("🔍 UNLOCK CHECK: Inactivity-based: Last active {}, Duration {} >= Required {}?",
@@ -528,37 +1101,38 @@ async fn check_unlock_conditions(config: &VaultConfig) -> Result<bool, VaultErro
         }
     }
 
-    // 3. Approval Threshold check
+    // 3. Approval Threshold check (member counts and weighted sums must all be met)
     let required_heirs = conditions.required_heir_approvals.unwrap_or(0);
     let required_witnesses = conditions.required_witness_approvals.unwrap_or(0);
+    let required_heir_weight = conditions.required_heir_approval_weight.unwrap_or(0);
+    let required_witness_weight = conditions.required_witness_approval_weight.unwrap_or(0);
 
-    ic_cdk::print(format!("🔍 UNLOCK CHECK: Approval-based: Heirs {}/{}, Witnesses {}/{} required.",
-        0, // Placeholder, actual count fetched below
-        required_heirs,
-        0, // Placeholder
-        required_witnesses
-    ));
-
-    if required_heirs > 0 || required_witnesses > 0 {
+    if required_heirs > 0 || required_witnesses > 0 || required_heir_weight > 0 || required_witness_weight > 0 {
         // Assume storage::approvals::get_approval_status exists and returns counts
         match storage::approvals::get_approval_status(vault_id).await {
             Ok(approvals) => {
-                 ic_cdk::print(format!("🔍 UNLOCK CHECK: Fetched approvals: Heirs {}, Witnesses {}.",
-                    approvals.heir_approvals, approvals.witness_approvals));
-                 if approvals.heir_approvals >= required_heirs && approvals.witness_approvals >= required_witnesses {
+                let heir_weight = approved_weight(vault_id, Role::Heir);
+                let witness_weight = approved_weight(vault_id, Role::Witness);
+                ic_cdk::print(format!("🔍 UNLOCK CHECK: Fetched approvals: Heirs {} (weight {}), Witnesses {} (weight {}).",
+                    approvals.heir_approvals, heir_weight, approvals.witness_approvals, witness_weight));
+                if approvals.heir_approvals >= required_heirs
+                    && approvals.witness_approvals >= required_witnesses
+                    && heir_weight >= required_heir_weight
+                    && witness_weight >= required_witness_weight
+                {
                     ic_cdk::print(format!(
-                        "✅ UNLOCK CHECK: Vault {} passed approval threshold (Heirs: {}/{}, Witnesses: {}/{}).",
+                        "✅ UNLOCK CHECK: Vault {} passed approval threshold (Heirs: {}/{}, weight {}/{}; Witnesses: {}/{}, weight {}/{}).",
                         vault_id,
-                        approvals.heir_approvals, required_heirs,
-                        approvals.witness_approvals, required_witnesses
+                        approvals.heir_approvals, required_heirs, heir_weight, required_heir_weight,
+                        approvals.witness_approvals, required_witnesses, witness_weight, required_witness_weight
                     ));
                     return Ok(true); // Approvals met
                 } else {
-                     ic_cdk::print(format!(
-                        "⏳ UNLOCK CHECK: Vault {} pending approvals (Heirs: {}/{}, Witnesses: {}/{}).",
+                    ic_cdk::print(format!(
+                        "⏳ UNLOCK CHECK: Vault {} pending approvals (Heirs: {}/{}, weight {}/{}; Witnesses: {}/{}, weight {}/{}).",
                         vault_id,
-                        approvals.heir_approvals, required_heirs,
-                        approvals.witness_approvals, required_witnesses
+                        approvals.heir_approvals, required_heirs, heir_weight, required_heir_weight,
+                        approvals.witness_approvals, required_witnesses, witness_weight, required_witness_weight
                     ));
                     // Continue checking other conditions
                 }
@@ -577,6 +1151,19 @@ async fn check_unlock_conditions(config: &VaultConfig) -> Result<bool, VaultErro
          ic_cdk::print("🔍 UNLOCK CHECK: Approval condition not configured (0 heirs/witnesses required).");
     }
 
+    // 4. Authority attestation check
+    if let Some(required) = conditions.required_death_attestations.filter(|n| *n > 0) {
+        let attested = attestation_service::count_trusted_attestations(vault_id, AttestationKind::Death);
+        ic_cdk::print(format!("🔍 UNLOCK CHECK: Death attestations {}/{} required.", attested, required));
+        if attested >= required {
+            ic_cdk::print(format!(
+                "✅ UNLOCK CHECK: Vault {} passed authority attestation condition ({}/{}).",
+                vault_id, attested, required
+            ));
+            return Ok(true);
+        }
+    }
+
     // If none of the conditions were met after checking all configured ones
     ic_cdk::print(format!("⏳ UNLOCK CHECK: Vault {} - NO conditions met.", vault_id));
     Ok(false)
@@ -606,7 +1193,11 @@ pub async fn get_vaults_by_owner(owner: PrincipalId) -> Result<Vec<VaultConfig>,
 /// # Returns
 /// * `Result<Vec<VaultConfig>, VaultError>` - A list of vault configurations or an error.
 pub async fn get_vaults_by_member(member_principal: PrincipalId) -> Result<Vec<VaultConfig>, VaultError> {
-    let member_vaults = storage::get_vaults_by_member(member_principal);
+    // Deleted vaults (in the trash or being purged) are only listed for their owners
+    let member_vaults = storage::get_vaults_by_member(member_principal)
+        .into_iter()
+        .filter(|config| config.status != VaultStatus::Deleted)
+        .collect();
     Ok(member_vaults)
 }
 
@@ -634,6 +1225,7 @@ pub async fn list_all_vaults(offset: u64, limit: usize) -> Result<(Vec<VaultConf
 
 /// Deletes a vault and potentially associated data.
 /// Requires owner authorization and specific vault status (e.g., Expired).
+/// On jointly approved vaults, owners must propose deletion as a joint action.
 ///
 /// # Arguments
 /// * `vault_id` - The ID of the vault to delete.
@@ -643,16 +1235,28 @@ pub async fn list_all_vaults(offset: u64, limit: usize) -> Result<(Vec<VaultConf
 /// * `Result<(), VaultError>` - Success or an error.
 pub async fn delete_vault(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
     let config = get_vault_config(vault_id).await?;
+    if config.is_owner(&caller) && joint_approval_required(&config) {
+        return Err(VaultError::JointApprovalRequired("Deletion must be proposed as a joint action".to_string()));
+    }
+    execute_delete_vault(vault_id, caller).await
+}
+
+/// Deletes a vault without the joint approval check (used once a joint action is approved).
+pub(crate) async fn execute_delete_vault(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = get_vault_config(vault_id).await?;
 
-    // 1. Authorization Check: Ensure caller is the owner or admin
-    let is_admin = storage::config::get_admin_principal().await? == caller;
-    if config.owner != caller && !is_admin {
+    // 1. Authorization Check: Ensure caller is an owner allowed to change settings, or admin
+    let is_admin = storage::config::get_admin_principal() == caller;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) && !is_admin {
         return Err(VaultError::NotAuthorized(format!(
             "Caller {} is not the owner or admin of vault {}",
             caller, vault_id
         )));
     }
 
+    // Held vaults cannot be deleted, not even by admin, until the hold is released
+    config.check_legal_hold()?;
+
     // 2. Status Check: Allow deletion from Expired or potentially other states if admin.
     // For now, let's restrict non-admins to Expired or Deleted (for idempotency).
     if !is_admin && !matches!(config.status, VaultStatus::Expired | VaultStatus::Deleted) {
@@ -662,81 +1266,135 @@ pub async fn delete_vault(vault_id: &VaultId, caller: PrincipalId) -> Result<(),
         )));
     }
 
-    // If already Deleted, return Ok for idempotency
-    if config.status == VaultStatus::Deleted {
-        ic_cdk::print(format!("ℹ️ INFO: Vault {} is already marked as Deleted.", vault_id));
-        return Ok(());
-    }
-
     let trigger_info = if is_admin { "admin" } else { "owner" };
     ic_cdk::print(format!("🗑️ INFO: Initiating deletion for vault {} by {}", vault_id, trigger_info));
 
-    // --- Cleanup Steps (Placeholders - Require Implementation) ---
+    // Note: Billing records are likely kept for historical purposes and not deleted.
+    soft_delete_vault(&mut config, caller)
+}
 
-    // Remove members
-    match storage::members::remove_members_by_vault(vault_id).await {
-        Ok(count) => ic_cdk::print(format!("🗑️ INFO: Removed {} members for vault {}", count, vault_id)),
-        Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed removing members for vault {}: {:?}", vault_id, e), // Log error, continue deletion
+/// Moves an authorized deletion into the trash for the vault's restore window; the scheduler starts
+/// the deletion job once the window ends. Without a restore window (or for a vault already marked
+/// Deleted) the job starts now. Shared by `execute_delete_vault` and the scheduler's expiry path;
+/// vaults trashed by the scheduler cannot be restored.
+pub(crate) fn soft_delete_vault(config: &mut VaultConfig, deleted_by: PrincipalId) -> Result<(), VaultError> {
+    let vault_id = config.vault_id;
+    if config.soft_deleted.is_some() {
+        ic_cdk::print(format!("ℹ️ INFO: Vault {} is already in the trash.", vault_id));
+        return Ok(());
     }
 
-    // Remove content metadata (chunk removal assumed handled elsewhere or not needed)
-    match storage::content::remove_all_content_for_vault(vault_id).await {
-         Ok(count) => ic_cdk::print(format!("🗑️ INFO: Removed {} content items for vault {}", count, vault_id)),
-         Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed removing content for vault {}: {:?}", vault_id, e), // Log error, continue deletion
-    }
-    // Remove content index
-    match storage::content_index::remove_index(vault_id).await {
-        Ok(_) => ic_cdk::print(format!("🗑️ INFO: Removed content index for vault {}", vault_id)),
-        Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed removing content index for vault {}: {:?}", vault_id, e),
+    // Records are purged in batches by the deletion job; the vault stays visible as Deleted until it finishes
+    if config.restore_window_days == 0 || config.status == VaultStatus::Deleted {
+        deletion_service::start_deletion(&vault_id, deleted_by)?;
+        return Ok(());
     }
 
-    // Remove invite tokens
-    match storage::tokens::remove_tokens_by_vault(vault_id).await {
-        Ok(count) => ic_cdk::print(format!("🗑️ INFO: Removed {} tokens for vault {}", count, vault_id)),
-        Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed removing tokens for vault {}: {:?}", vault_id, e), // Log error, continue deletion
+    let current_time = time();
+    config.soft_deleted = Some(SoftDeletion {
+        deleted_at: current_time,
+        deleted_by,
+        previous_status: config.status,
+        unlocked_at: config.unlocked_at,
+    });
+    apply_status_change(config, VaultStatus::Deleted, Some(deleted_by))?;
+    let details = if deleted_by == ic_cdk::api::id() {
+        format!("Vault moved to trash after its term ended, deletion starts at {}", config.restore_deadline(current_time))
+    } else {
+        format!("Vault moved to trash, restorable until {}", config.restore_deadline(current_time))
+    };
+    storage::audit_logs::record(&vault_id, LogAction::VaultDeleted, deleted_by, details);
+    Ok(())
+}
+
+/// Restores a soft-deleted vault to the status it had before deletion.
+/// Owner or admin, until the restore window ends. Vaults the scheduler trashed when their term
+/// ran out are not restorable.
+pub async fn restore_vault(vault_id: &VaultId, caller: PrincipalId) -> Result<(), VaultError> {
+    let mut config = get_vault_config(vault_id).await?;
+    let is_admin = storage::config::get_admin_principal() == caller;
+    if !config.has_owner_permission(&caller, OwnerPermission::ManageSettings) && !is_admin {
+        return Err(VaultError::NotAuthorized(format!(
+            "Caller {} is not the owner or admin of vault {}",
+            caller, vault_id
+        )));
     }
 
-    // Remove audit logs
-    match storage::audit_logs::remove_audit_logs(vault_id).await {
-        Ok(_) => ic_cdk::print(format!("🗑️ INFO: Removed audit logs for vault {}", vault_id)),
-        Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed removing audit logs for vault {}: {:?}", vault_id, e), // Log error, continue deletion
+    config.check_legal_hold()?;
+
+    let deletion = config.soft_deleted.clone()
+        .ok_or_else(|| VaultError::InvalidState(format!("Vault {} is not in the trash", vault_id)))?;
+    let current_time = time();
+    if current_time >= config.restore_deadline(deletion.deleted_at) || storage::deletion_jobs::get_job(vault_id).is_some() {
+        return Err(VaultError::InvalidState(format!("The restore window of vault {} has ended", vault_id)));
+    }
+    // Expired and heir-grace vaults are trashed by the scheduler; restored as they were, its next run trashes them again
+    if deletion.deleted_by == ic_cdk::api::id() {
+        return Err(VaultError::InvalidState(format!("Vault {} was removed after its term ended and cannot be restored", vault_id)));
     }
 
-    // Remove approvals
-    match storage::approvals::remove_approvals(vault_id).await {
-         Ok(_) => ic_cdk::print(format!("🗑️ INFO: Removed approvals for vault {}", vault_id)),
-         Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed removing approvals for vault {}: {:?}", vault_id, e), // Log error, continue deletion
+    config.status = deletion.previous_status;
+    config.unlocked_at = deletion.unlocked_at;
+    config.soft_deleted = None;
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+    ic_cdk::print(format!("♻️ INFO: Vault {} restored to {:?} by {}", vault_id, config.status, caller));
+    storage::audit_logs::record(vault_id, LogAction::VaultRestored, caller, format!("Vault restored to {:?}", config.status));
+    Ok(())
+}
+
+/// Places a legal hold on a vault. Admin only. The hold freezes status changes, deletion,
+/// unlock and downloads; metadata stays readable. Replaces the reason of an existing hold.
+pub async fn set_legal_hold(vault_id: &VaultId, reason: String, caller: PrincipalId) -> Result<(), VaultError> {
+    if storage::config::get_admin_principal() != caller {
+        return Err(VaultError::AdminGuardFailed);
+    }
+    let reason = reason.trim().to_string();
+    if reason.is_empty() || reason.len() > MAX_LEGAL_HOLD_REASON_LEN {
+        return Err(VaultError::InvalidInput(format!("A reason of 1 to {} characters is required", MAX_LEGAL_HOLD_REASON_LEN)));
     }
+    let mut config = get_vault_config(vault_id).await?;
 
-    // --- Final Step: Remove Vault Config & Update Metrics ---
-    match storage::vault_configs::remove_vault_config(vault_id).await {
-        Ok(Some(_)) => {
-            ic_cdk::print(format!("✅ SUCCESS: Vault {} configuration removed.", vault_id));
-            // Update metrics
-            match storage::metrics::decrement_vault_count().await {
-                Ok(_) => ic_cdk::print(format!("📊 INFO: Decremented vault count metric.")),
-                Err(e) => ic_cdk::eprintln!("❌ ERROR: Failed decrementing vault count metric: {:?}", e),
-            }
-             Ok(())
-        }
-        Ok(None) => {
-             ic_cdk::eprintln!("⚠️ WARNING: Vault {} config already removed during deletion process.", vault_id);
-             Ok(()) // Idempotent
-        }
-        Err(e) => {
-            ic_cdk::eprintln!("❌ ERROR: Failed to remove vault {} config during deletion: {:?}", vault_id, e);
-            Err(VaultError::StorageError(format!("Failed to remove vault config: {}", e)))
-        }
+    let current_time = time();
+    config.legal_hold = Some(LegalHold { reason: reason.clone(), placed_by: caller, placed_at: current_time });
+    config.updated_at = current_time;
+    storage::vault_configs::insert_vault_config(&config);
+    ic_cdk::print(format!("⚖️ INFO: Legal hold placed on vault {} by {}", vault_id, caller));
+    storage::audit_logs::record(vault_id, LogAction::LegalHoldPlaced, caller, format!("Legal hold placed: {}", reason));
+    Ok(())
+}
+
+/// Releases the legal hold of a vault. Admin only; the reason is kept in the audit log.
+pub async fn release_legal_hold(vault_id: &VaultId, reason: String, caller: PrincipalId) -> Result<(), VaultError> {
+    if storage::config::get_admin_principal() != caller {
+        return Err(VaultError::AdminGuardFailed);
+    }
+    let reason = reason.trim().to_string();
+    if reason.is_empty() || reason.len() > MAX_LEGAL_HOLD_REASON_LEN {
+        return Err(VaultError::InvalidInput(format!("A reason of 1 to {} characters is required", MAX_LEGAL_HOLD_REASON_LEN)));
+    }
+    let mut config = get_vault_config(vault_id).await?;
+    if config.legal_hold.is_none() {
+        return Err(VaultError::InvalidState(format!("Vault {} is not under legal hold", vault_id)));
     }
 
-    // Note: Billing records are likely kept for historical purposes and not deleted.
+    config.legal_hold = None;
+    config.updated_at = time();
+    // A renewal paid while the vault was held extended the term but left the grace status
+    if matches!(config.status, VaultStatus::GraceMaster | VaultStatus::GraceHeir) && config.expires_at > config.updated_at {
+        apply_status_change(&mut config, VaultStatus::Active, Some(caller))?;
+    } else {
+        storage::vault_configs::insert_vault_config(&config);
+    }
+    ic_cdk::print(format!("⚖️ INFO: Legal hold released on vault {} by {}", vault_id, caller));
+    storage::audit_logs::record(vault_id, LogAction::LegalHoldReleased, caller, format!("Legal hold released: {}", reason));
+    Ok(())
 }
 
 /// Internal helper function to update the storage usage for a vault.
 /// Checks against the quota.
 async fn update_storage_usage(vault_id: &VaultId, delta_bytes: i64) -> Result<(), VaultError> {
-    let mut config = storage::vault_configs::get_vault_config(vault_id)
-        .ok_or_else(|| VaultError::VaultNotFound(vault_id.clone().to_string()))?;
+    let mut config = storage::vault_configs::require(vault_id)?;
 
     let mut new_usage = config.storage_used_bytes as i64 + delta_bytes;
 
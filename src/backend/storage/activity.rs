@@ -0,0 +1,58 @@
+// src/backend/storage/activity.rs
+use crate::models::activity::ActivityRecord;
+use crate::models::common::VaultId;
+use crate::storage::memory::{get_owner_activity_memory, Memory};
+use crate::storage::storable::Cbor;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+/// Maximum number of activity records kept per vault (oldest are dropped first).
+const MAX_ACTIVITY_RECORDS: usize = 50;
+
+type ActivityMap = StableBTreeMap<VaultId, Cbor<Vec<ActivityRecord>>, Memory>;
+
+thread_local! {
+    /// Stable storage for recent owner activity per vault.
+    /// Key: VaultId (Principal)
+    /// Value: Cbor<Vec<ActivityRecord>> (oldest first)
+    static ACTIVITY: RefCell<ActivityMap> = RefCell::new(
+        ActivityMap::init(get_owner_activity_memory())
+    );
+}
+
+/// Appends an activity record for a vault, keeping only the most recent entries.
+pub fn add_activity(vault_id: &VaultId, record: ActivityRecord) {
+    ACTIVITY.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let mut records = map.get(vault_id).map(|c| c.0).unwrap_or_default();
+        records.push(record);
+        if records.len() > MAX_ACTIVITY_RECORDS {
+            let excess = records.len() - MAX_ACTIVITY_RECORDS;
+            records.drain(..excess);
+        }
+        map.insert(*vault_id, Cbor(records));
+    });
+}
+
+/// Retrieves the recent activity records for a vault (oldest first).
+pub fn get_activity(vault_id: &VaultId) -> Vec<ActivityRecord> {
+    ACTIVITY.with(|map_ref| map_ref.borrow().get(vault_id).map(|c| c.0).unwrap_or_default())
+}
+
+/// Removes up to `limit` activity records of a vault during deletion, dropping the entry once it is empty.
+/// Returns the number removed; 0 once the vault has none left.
+pub fn remove_activity_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    ACTIVITY.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let mut records: Vec<ActivityRecord> = match map.remove(&*vault_id) {
+            Some(c) => c.0,
+            None => return 0,
+        };
+        let removed = records.len().min(limit);
+        records.drain(..removed);
+        if !records.is_empty() {
+            map.insert(*vault_id, Cbor(records));
+        }
+        removed as u64
+    })
+}
@@ -38,11 +38,9 @@ pub async fn get_approval_status(vault_id: &VaultId) -> Result<ApprovalCounts, V
 }
 
 /// Removes the approval record for a vault during deletion.
-pub async fn remove_approvals(vault_id: &VaultId) -> Result<(), VaultError> {
-    APPROVALS.with(|map_ref| {
-        map_ref.borrow_mut().remove(vault_id);
-    });
-    Ok(())
+/// Returns the number removed (0 or 1).
+pub fn remove_approvals(vault_id: &VaultId) -> u64 {
+    APPROVALS.with(|map_ref| map_ref.borrow_mut().remove(vault_id).is_some() as u64)
 }
 
 /// Records an approval for a specific role within a vault.
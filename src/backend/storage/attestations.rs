@@ -0,0 +1,91 @@
+// src/backend/storage/attestations.rs
+use crate::models::attestation::{EventAttestation, TrustedAuthority};
+use crate::models::common::{PrincipalId, VaultId};
+use crate::storage::memory::{get_attestations_memory, get_trusted_authorities_memory, Memory};
+use crate::storage::storable::Cbor;
+use candid::Principal;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+type AuthoritiesMap = StableBTreeMap<PrincipalId, Cbor<TrustedAuthority>, Memory>;
+type AttestationsMap = StableBTreeMap<(VaultId, PrincipalId), Cbor<Vec<EventAttestation>>, Memory>;
+
+thread_local! {
+    /// Registry of trusted authority principals.
+    static AUTHORITIES: RefCell<AuthoritiesMap> = RefCell::new(
+        AuthoritiesMap::init(get_trusted_authorities_memory())
+    );
+
+    /// Attestations per vault and authority.
+    /// Key: (VaultId, authority PrincipalId)
+    /// Value: Cbor<Vec<EventAttestation>> (at most one per kind)
+    static ATTESTATIONS: RefCell<AttestationsMap> = RefCell::new(
+        AttestationsMap::init(get_attestations_memory())
+    );
+}
+
+pub fn insert_authority(authority: TrustedAuthority) {
+    AUTHORITIES.with(|map_ref| {
+        map_ref.borrow_mut().insert(authority.principal, Cbor(authority));
+    });
+}
+
+pub fn remove_authority(principal: &PrincipalId) -> Option<TrustedAuthority> {
+    AUTHORITIES.with(|map_ref| map_ref.borrow_mut().remove(principal).map(|c| c.0))
+}
+
+pub fn is_trusted_authority(principal: &PrincipalId) -> bool {
+    AUTHORITIES.with(|map_ref| map_ref.borrow().contains_key(principal))
+}
+
+/// All trusted authorities, ordered by principal.
+pub fn get_authorities() -> Vec<TrustedAuthority> {
+    AUTHORITIES.with(|map_ref| map_ref.borrow().iter().map(|(_, c)| c.0).collect())
+}
+
+/// Records an attestation, unless the authority already attested this kind for the vault.
+/// Returns false for a duplicate.
+pub fn add_attestation(attestation: EventAttestation) -> bool {
+    ATTESTATIONS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let key = (attestation.vault_id, attestation.authority);
+        let mut attestations = map.get(&key).map(|c| c.0).unwrap_or_default();
+        if attestations.iter().any(|a| a.kind == attestation.kind) {
+            return false;
+        }
+        attestations.push(attestation);
+        map.insert(key, Cbor(attestations));
+        true
+    })
+}
+
+/// All attestations filed for a vault.
+pub fn get_vault_attestations(vault_id: &VaultId) -> Vec<EventAttestation> {
+    ATTESTATIONS.with(|map_ref| {
+        map_ref.borrow()
+            .range((*vault_id, Principal::management_canister())..)
+            .take_while(|((v, _), _)| v == vault_id)
+            .flat_map(|(_, c)| c.0)
+            .collect()
+    })
+}
+
+/// Removes up to `limit` attestations of a vault during deletion.
+/// Returns the number removed; 0 once the vault has none left.
+pub fn remove_vault_attestations_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    ATTESTATIONS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let keys: Vec<(VaultId, PrincipalId)> = map
+            .range((*vault_id, Principal::management_canister())..)
+            .take_while(|((v, _), _)| v == vault_id)
+            .take(limit)
+            .map(|(k, _)| k)
+            .collect();
+        let mut removed = 0u64;
+        for key in &keys {
+            // Count an authority's empty list as one record so the step still makes progress
+            removed += map.remove(key).map_or(0, |c| c.0.len().max(1) as u64);
+        }
+        removed
+    })
+}
@@ -1,10 +1,10 @@
 use crate::storage::memory::{get_audit_log_data_memory, Memory}; // Assuming data memory is sufficient for map
 use crate::storage::storable::{Cbor, StorableString};
-use crate::models::audit_log::AuditLogEntry;
+use crate::models::audit_log::{AuditLogEntry, LogAction};
 use ic_stable_structures::StableBTreeMap;
 use std::cell::RefCell;
 use ic_cdk::api::time;
-use crate::models::common::VaultId;
+use crate::models::common::{PrincipalId, VaultId};
 
 // Using Vec for now as in structures.rs. Consider StableLog if append-only is sufficient.
 type StorableAuditLogVec = Cbor<Vec<AuditLogEntry>>;
@@ -27,6 +27,14 @@ fn create_audit_log_key(vault_id: &str) -> StorableString {
     Cbor(format!("audit:{}", vault_id))
 }
 
+/// Records a service action in the vault's audit log. Failures are logged, never propagated,
+/// so a full or broken log does not fail the action itself.
+pub fn record(vault_id: &VaultId, action: LogAction, actor: PrincipalId, details: String) {
+    if let Err(e) = add_entry(&vault_id.to_text(), AuditLogEntry::new(action, actor, Some(details))) {
+        ic_cdk::eprintln!("❌ ERROR: Failed to write audit entry for vault {}: {}", vault_id, e);
+    }
+}
+
 /// Helper function to add an audit log entry for a specific vault.
 /// It retrieves the current log vector, appends the new entry, and saves it back.
 /// Note: This can be potentially expensive for very long logs. Capping/rotation might be needed later.
@@ -105,13 +113,23 @@ pub fn compact_log(vault_id_str: &str, max_entries: usize) -> Result<(), String>
     })
 }
 
-/// Removes the audit log entry for a given vault ID.
-pub async fn remove_audit_logs(vault_id: &VaultId) -> Result<(), String> {
+/// Removes up to `limit` audit log entries of a vault during deletion, dropping the entry once it is empty.
+/// Returns the number removed; 0 once the vault has none left.
+pub fn remove_audit_logs_batch(vault_id: &VaultId, limit: usize) -> u64 {
     let key = create_audit_log_key(&vault_id.to_string());
     LOGS.with(|map_ref| {
-        map_ref.borrow_mut().remove(&key);
-    });
-    Ok(())
+        let mut map = map_ref.borrow_mut();
+        let mut records: Vec<AuditLogEntry> = match map.remove(&key) {
+            Some(c) => c.0,
+            None => return 0,
+        };
+        let removed = records.len().min(limit);
+        records.drain(..removed);
+        if !records.is_empty() {
+            map.insert(key, Cbor(records));
+        }
+        removed as u64
+    })
 }
 
 // Note: No need to log compaction/rotation.
\ No newline at end of file
@@ -1,5 +1,5 @@
 // src/backend/storage/config.rs
-use crate::storage::memory::{get_admin_principal_memory, get_cron_principal_memory, get_min_cycles_threshold_memory, Memory};
+use crate::storage::memory::{get_admin_principal_memory, get_cron_principal_memory, get_draft_expiry_days_memory, get_min_cycles_threshold_memory, Memory};
 use crate::storage::storable::Cbor; // Assuming Principal uses Cbor
 use candid::Principal;
 use ic_stable_structures::StableCell;
@@ -9,6 +9,7 @@ use std::cell::RefCell;
 const DEFAULT_ADMIN_PRINCIPAL: Principal = Principal::management_canister();
 const DEFAULT_CRON_PRINCIPAL: Principal = Principal::management_canister();
 const DEFAULT_MIN_CYCLES_THRESHOLD: u128 = 10_000_000_000; // 10B cycles
+const DEFAULT_DRAFT_EXPIRY_DAYS: u32 = 7;
 
 thread_local! {
     /// Stable cell for the Admin Principal
@@ -28,6 +29,12 @@ thread_local! {
         StableCell::init(get_min_cycles_threshold_memory(), DEFAULT_MIN_CYCLES_THRESHOLD)
             .expect("Failed to initialize min cycles threshold stable cell")
     );
+
+    /// Stable cell for the number of days an unpaid Draft vault is kept (0 = never expire)
+    static DRAFT_EXPIRY_DAYS: RefCell<StableCell<u32, Memory>> = RefCell::new(
+        StableCell::init(get_draft_expiry_days_memory(), DEFAULT_DRAFT_EXPIRY_DAYS)
+            .expect("Failed to initialize draft expiry days stable cell")
+    );
 }
 
 /// Initialize the configuration values from InitArgs.
@@ -64,4 +71,18 @@ pub fn get_cron_principal() -> Principal {
 /// Get the configured Minimum Cycles Threshold.
 pub fn get_min_cycles_threshold() -> u128 {
     MIN_CYCLES_THRESHOLD.with(|cell| *cell.borrow().get())
-} 
\ No newline at end of file
+} 
+
+/// Get the number of days an unpaid Draft vault is kept before the scheduler removes it.
+pub fn get_draft_expiry_days() -> u32 {
+    DRAFT_EXPIRY_DAYS.with(|cell| *cell.borrow().get())
+}
+
+/// Set the Draft vault expiry period in days. 0 disables Draft expiry.
+pub fn set_draft_expiry_days(days: u32) {
+    DRAFT_EXPIRY_DAYS.with(|cell| {
+        cell.borrow_mut()
+            .set(days)
+            .expect("Failed to set draft expiry days");
+    });
+}
@@ -157,4 +157,23 @@ pub async fn remove_all_content_for_vault(vault_id: &VaultId) -> Result<u64, Vau
             errors.join("; ")
         )))
     }
-}
\ No newline at end of file
+}
+/// Removes up to `limit` content items of a vault, indexed or not (deletion jobs).
+/// Returns the number of items removed and their bytes (including retained versions); 0 items once none are left.
+pub fn remove_content_batch(vault_id: &VaultId, limit: usize) -> Result<(u64, u64), VaultError> {
+    let batch: Vec<(u64, Principal, u64)> = CONTENT_MAP.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .filter(|(_, item)| item.0.vault_id == *vault_id)
+            .take(limit)
+            .map(|(internal_id, item)| (internal_id, item.0.content_id, item.0.retained_bytes()))
+            .collect()
+    });
+
+    let mut bytes_freed = 0u64;
+    for (internal_id, content_id, size) in &batch {
+        remove_content(*internal_id, *content_id)?;
+        bytes_freed = bytes_freed.saturating_add(*size);
+    }
+    Ok((batch.len() as u64, bytes_freed))
+}
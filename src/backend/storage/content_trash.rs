@@ -0,0 +1,113 @@
+// src/backend/storage/content_trash.rs
+use crate::models::common::{ContentId, Timestamp, VaultId};
+use crate::storage::memory::{get_content_trash_memory, get_content_trash_vault_idx_memory, Memory};
+use candid::Principal;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+// Key: (purge_at, ContentId), so items due for purging are a prefix scan
+// Value: VaultId of the content item
+type TrashMap = StableBTreeMap<(Timestamp, ContentId), VaultId, Memory>;
+// Secondary index: (VaultId, ContentId) -> purge_at, for per-vault range scans
+type VaultIndex = StableBTreeMap<(VaultId, ContentId), Timestamp, Memory>;
+
+thread_local! {
+    /// Soft-deleted content items awaiting purge, ordered by the end of their restore window.
+    /// The item itself (with `deleted_at` set) stays in content storage until purged.
+    static TRASH: RefCell<TrashMap> = RefCell::new(
+        TrashMap::init(get_content_trash_memory())
+    );
+
+    static TRASH_VAULT_INDEX: RefCell<VaultIndex> = RefCell::new(
+        VaultIndex::init(get_content_trash_vault_idx_memory())
+    );
+}
+
+/// Adds a soft-deleted item, to be purged at `purge_at`.
+pub fn add(purge_at: Timestamp, content_id: ContentId, vault_id: VaultId) {
+    TRASH.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        remove_in(&mut map, &content_id);
+        map.insert((purge_at, content_id), vault_id);
+    });
+    TRASH_VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().insert((vault_id, content_id), purge_at));
+}
+
+/// Removes an item from the trash (restored or purged), if present.
+pub fn remove(content_id: &ContentId) {
+    TRASH.with(|map_ref| remove_in(&mut map_ref.borrow_mut(), content_id));
+}
+
+fn remove_in(map: &mut TrashMap, content_id: &ContentId) {
+    let keys: Vec<(Timestamp, ContentId)> = map.iter().map(|(k, _)| k).filter(|(_, c)| c == content_id).collect();
+    for key in keys {
+        if let Some(vault_id) = map.remove(&key) {
+            TRASH_VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&(vault_id, *content_id)));
+        }
+    }
+}
+
+/// When a soft-deleted item will be purged, if it is in the trash.
+pub fn get_purge_at(content_id: &ContentId) -> Option<Timestamp> {
+    TRASH.with(|map_ref| {
+        map_ref.borrow().iter().map(|(k, _)| k).find(|(_, c)| c == content_id).map(|(purge_at, _)| purge_at)
+    })
+}
+
+/// Returns the items whose restore window has ended (purge_at <= current_time).
+pub fn get_due(current_time: Timestamp) -> Vec<(ContentId, VaultId)> {
+    TRASH.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .take_while(|((purge_at, _), _)| *purge_at <= current_time)
+            .map(|((_, content_id), vault_id)| (content_id, vault_id))
+            .collect()
+    })
+}
+
+/// Removes up to `limit` trash entries of a vault via the vault index (deletion jobs).
+/// Returns the number removed; 0 once the vault has none left.
+pub fn remove_vault_entries_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    let entries: Vec<(ContentId, Timestamp)> = TRASH_VAULT_INDEX.with(|index_ref| {
+        index_ref.borrow()
+            .range((*vault_id, Principal::management_canister())..)
+            .take_while(|((vid, _), _)| vid == vault_id)
+            .take(limit)
+            .map(|((_, content_id), purge_at)| (content_id, purge_at))
+            .collect()
+    });
+    TRASH.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        for (content_id, purge_at) in &entries {
+            map.remove(&(*purge_at, *content_id));
+        }
+    });
+    TRASH_VAULT_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        for (content_id, _) in &entries {
+            index.remove(&(*vault_id, *content_id));
+        }
+    });
+    entries.len() as u64
+}
+
+/// Rebuilds the per-vault index from the trash entries (after an upgrade that introduced it).
+/// Does nothing when the index already has entries.
+pub fn rebuild_vault_index() -> u64 {
+    if TRASH_VAULT_INDEX.with(|index_ref| !index_ref.borrow().is_empty()) {
+        return 0;
+    }
+    let keys: Vec<((VaultId, ContentId), Timestamp)> = TRASH.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .map(|((purge_at, content_id), vault_id)| ((vault_id, content_id), purge_at))
+            .collect()
+    });
+    TRASH_VAULT_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        for (key, purge_at) in &keys {
+            index.insert(*key, *purge_at);
+        }
+    });
+    keys.len() as u64
+}
@@ -0,0 +1,63 @@
+// src/backend/storage/content_versions.rs
+use crate::models::common::ContentId;
+use crate::models::vault_content_item::VersionBlob;
+use crate::storage::memory::{get_content_versions_memory, Memory};
+use crate::storage::storable::Cbor;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+type VersionsMap = StableBTreeMap<(ContentId, u32), Cbor<VersionBlob>, Memory>;
+
+thread_local! {
+    /// Payloads of previous content versions.
+    /// Key: (ContentId, version)
+    /// Value: Cbor<VersionBlob>
+    static VERSIONS: RefCell<VersionsMap> = RefCell::new(
+        VersionsMap::init(get_content_versions_memory())
+    );
+}
+
+pub fn insert_version(content_id: ContentId, version: u32, blob: VersionBlob) {
+    VERSIONS.with(|map_ref| {
+        map_ref.borrow_mut().insert((content_id, version), Cbor(blob));
+    });
+}
+
+pub fn get_version(content_id: &ContentId, version: u32) -> Option<VersionBlob> {
+    VERSIONS.with(|map_ref| map_ref.borrow().get(&(*content_id, version)).map(|c| c.0))
+}
+
+pub fn remove_version(content_id: &ContentId, version: u32) -> Option<VersionBlob> {
+    VERSIONS.with(|map_ref| map_ref.borrow_mut().remove(&(*content_id, version)).map(|c| c.0))
+}
+
+/// Removes every retained version of a content item.
+pub fn remove_item_versions(content_id: &ContentId) {
+    VERSIONS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let keys: Vec<(ContentId, u32)> = map.range((*content_id, 0)..=(*content_id, u32::MAX)).map(|(k, _)| k).collect();
+        for key in keys {
+            map.remove(&key);
+        }
+    });
+}
+
+/// Removes up to `limit` retained versions of the given content items (deletion jobs).
+/// Only the items' own key ranges are visited. Returns the number removed; 0 once none are left.
+pub fn remove_versions_batch(content_ids: &[ContentId], limit: usize) -> u64 {
+    VERSIONS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let mut keys: Vec<(ContentId, u32)> = Vec::new();
+        for content_id in content_ids {
+            if keys.len() >= limit {
+                break;
+            }
+            let remaining = limit - keys.len();
+            keys.extend(map.range((*content_id, 0)..=(*content_id, u32::MAX)).map(|(k, _)| k).take(remaining));
+        }
+        for key in &keys {
+            map.remove(key);
+        }
+        keys.len() as u64
+    })
+}
@@ -0,0 +1,52 @@
+// src/backend/storage/deletion_jobs.rs
+use crate::models::common::VaultId;
+use crate::models::deletion::{DeletionJob, VaultTombstone};
+use crate::storage::memory::{get_deletion_jobs_memory, get_vault_tombstones_memory, Memory};
+use crate::storage::storable::Cbor;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+type JobsMap = StableBTreeMap<VaultId, Cbor<DeletionJob>, Memory>;
+type TombstonesMap = StableBTreeMap<VaultId, Cbor<VaultTombstone>, Memory>;
+
+thread_local! {
+    /// Vault deletions in progress, one per vault. Removed once the tombstone is written.
+    static JOBS: RefCell<JobsMap> = RefCell::new(
+        JobsMap::init(get_deletion_jobs_memory())
+    );
+
+    /// Tombstones of fully deleted vaults, kept permanently.
+    static TOMBSTONES: RefCell<TombstonesMap> = RefCell::new(
+        TombstonesMap::init(get_vault_tombstones_memory())
+    );
+}
+
+/// Inserts or replaces the deletion job of a vault.
+pub fn upsert_job(job: &DeletionJob) {
+    JOBS.with(|map_ref| {
+        map_ref.borrow_mut().insert(job.vault_id, Cbor(job.clone()));
+    });
+}
+
+pub fn get_job(vault_id: &VaultId) -> Option<DeletionJob> {
+    JOBS.with(|map_ref| map_ref.borrow().get(vault_id).map(|c| c.0))
+}
+
+/// All deletion jobs in progress, ordered by vault id.
+pub fn get_jobs() -> Vec<DeletionJob> {
+    JOBS.with(|map_ref| map_ref.borrow().iter().map(|(_, c)| c.0).collect())
+}
+
+pub fn remove_job(vault_id: &VaultId) -> Option<DeletionJob> {
+    JOBS.with(|map_ref| map_ref.borrow_mut().remove(vault_id).map(|c| c.0))
+}
+
+pub fn insert_tombstone(tombstone: &VaultTombstone) {
+    TOMBSTONES.with(|map_ref| {
+        map_ref.borrow_mut().insert(tombstone.vault_id, Cbor(tombstone.clone()));
+    });
+}
+
+pub fn get_tombstone(vault_id: &VaultId) -> Option<VaultTombstone> {
+    TOMBSTONES.with(|map_ref| map_ref.borrow().get(vault_id).map(|c| c.0))
+}
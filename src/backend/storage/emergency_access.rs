@@ -0,0 +1,67 @@
+// src/backend/storage/emergency_access.rs
+use crate::models::common::{Timestamp, VaultId};
+use crate::models::emergency_access::{EmergencyAccessGrant, EmergencyAccessStatus};
+use crate::storage::memory::{get_emergency_access_memory, Memory};
+use crate::storage::storable::Cbor;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+type GrantsMap = StableBTreeMap<VaultId, Cbor<Vec<EmergencyAccessGrant>>, Memory>;
+
+thread_local! {
+    /// Stable storage for emergency access grants per vault.
+    /// Key: VaultId (Principal)
+    /// Value: Cbor<Vec<EmergencyAccessGrant>> (oldest first, resolved grants kept for history)
+    static GRANTS: RefCell<GrantsMap> = RefCell::new(
+        GrantsMap::init(get_emergency_access_memory())
+    );
+}
+
+/// Inserts a grant, or replaces the grant with the same id.
+pub fn upsert_grant(grant: EmergencyAccessGrant) {
+    GRANTS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let vault_id = grant.vault_id;
+        let mut grants = map.get(&vault_id).map(|c| c.0).unwrap_or_default();
+        match grants.iter_mut().find(|g| g.grant_id == grant.grant_id) {
+            Some(existing) => *existing = grant,
+            None => grants.push(grant),
+        }
+        map.insert(vault_id, Cbor(grants));
+    });
+}
+
+/// Retrieves all grants of a vault (oldest first).
+pub fn get_grants(vault_id: &VaultId) -> Vec<EmergencyAccessGrant> {
+    GRANTS.with(|map_ref| map_ref.borrow().get(vault_id).map(|c| c.0).unwrap_or_default())
+}
+
+/// Returns pending grants whose waiting period has ended, across all vaults.
+/// NOTE: This iterates the entire map, which can be inefficient.
+pub fn get_due_pending_grants(current_time: Timestamp) -> Vec<EmergencyAccessGrant> {
+    GRANTS.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .flat_map(|(_, c)| c.0)
+            .filter(|g| g.status == EmergencyAccessStatus::Pending && g.available_at <= current_time)
+            .collect()
+    })
+}
+
+/// Removes up to `limit` emergency access grants of a vault during deletion, dropping the entry once it is empty.
+/// Returns the number removed; 0 once the vault has none left.
+pub fn remove_grants_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    GRANTS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let mut records: Vec<EmergencyAccessGrant> = match map.remove(&*vault_id) {
+            Some(c) => c.0,
+            None => return 0,
+        };
+        let removed = records.len().min(limit);
+        records.drain(..removed);
+        if !records.is_empty() {
+            map.insert(*vault_id, Cbor(records));
+        }
+        removed as u64
+    })
+}
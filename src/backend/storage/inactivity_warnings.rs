@@ -0,0 +1,69 @@
+// src/backend/storage/inactivity_warnings.rs
+use crate::models::common::{Timestamp, VaultId};
+use crate::models::inactivity_warning::{InactivityWarning, InactivityWarningKind};
+use crate::storage::memory::{get_inactivity_warnings_memory, Memory};
+use crate::storage::storable::Cbor;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+/// Maximum number of warnings kept per vault (oldest are dropped first).
+const MAX_WARNINGS_PER_VAULT: usize = 100;
+
+type WarningsMap = StableBTreeMap<VaultId, Cbor<Vec<InactivityWarning>>, Memory>;
+
+thread_local! {
+    /// Stable storage for inactivity warnings issued per vault.
+    /// Key: VaultId (Principal)
+    /// Value: Cbor<Vec<InactivityWarning>> (oldest first)
+    static WARNINGS: RefCell<WarningsMap> = RefCell::new(
+        WarningsMap::init(get_inactivity_warnings_memory())
+    );
+}
+
+/// Appends a warning for its vault, keeping only the most recent entries.
+pub fn add_warning(warning: InactivityWarning) {
+    WARNINGS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let vault_id = warning.vault_id;
+        let mut warnings = map.get(&vault_id).map(|c| c.0).unwrap_or_default();
+        warnings.push(warning);
+        if warnings.len() > MAX_WARNINGS_PER_VAULT {
+            let excess = warnings.len() - MAX_WARNINGS_PER_VAULT;
+            warnings.drain(..excess);
+        }
+        map.insert(vault_id, Cbor(warnings));
+    });
+}
+
+/// Retrieves all warnings issued for a vault (oldest first).
+pub fn get_warnings(vault_id: &VaultId) -> Vec<InactivityWarning> {
+    WARNINGS.with(|map_ref| map_ref.borrow().get(vault_id).map(|c| c.0).unwrap_or_default())
+}
+
+/// Checks whether a warning of this kind was already issued for the given deadline
+/// at the same or a tighter threshold (`days_before` less than or equal to the given one).
+pub fn has_crossing(vault_id: &VaultId, deadline: Timestamp, days_before: u32, kind: InactivityWarningKind) -> bool {
+    WARNINGS.with(|map_ref| {
+        map_ref.borrow().get(vault_id).map_or(false, |c| {
+            c.0.iter().any(|w| w.deadline == deadline && w.kind == kind && w.days_before <= days_before)
+        })
+    })
+}
+
+/// Removes up to `limit` inactivity warnings of a vault during deletion, dropping the entry once it is empty.
+/// Returns the number removed; 0 once the vault has none left.
+pub fn remove_warnings_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    WARNINGS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let mut records: Vec<InactivityWarning> = match map.remove(&*vault_id) {
+            Some(c) => c.0,
+            None => return 0,
+        };
+        let removed = records.len().min(limit);
+        records.drain(..removed);
+        if !records.is_empty() {
+            map.insert(*vault_id, Cbor(records));
+        }
+        removed as u64
+    })
+}
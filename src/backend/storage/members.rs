@@ -124,4 +124,21 @@ pub async fn remove_members_by_vault(vault_id: &VaultId) -> Result<u64, VaultErr
     });
 
     Ok(removed_count)
-} 
\ No newline at end of file
+} 
+/// Removes up to `limit` members of a vault (deletion jobs).
+/// Returns the number removed; 0 once the vault has no members left.
+pub fn remove_members_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    MEMBERS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let keys: Vec<(VaultId, PrincipalId)> = map
+            .range((*vault_id, Principal::management_canister())..)
+            .take_while(|((vid, _), _)| vid == vault_id)
+            .take(limit)
+            .map(|(key, _)| key)
+            .collect();
+        for key in &keys {
+            map.remove(key);
+        }
+        keys.len() as u64
+    })
+}
@@ -37,6 +37,28 @@ const CRON_PRINCIPAL_MEM_ID: MemoryId = MemoryId::new(26);
 const MIN_CYCLES_THRESHOLD_MEM_ID: MemoryId = MemoryId::new(27);
 const UPLOAD_SESSIONS_MEM_ID: MemoryId = MemoryId::new(28);
 const UPLOAD_CHUNKS_MEM_ID: MemoryId = MemoryId::new(29);
+const OWNER_ACTIVITY_MEM_ID: MemoryId = MemoryId::new(30);
+const INACTIVITY_WARNINGS_MEM_ID: MemoryId = MemoryId::new(31);
+const NOTIFICATION_COUNTER_MEM_ID: MemoryId = MemoryId::new(32);
+const NOTIFICATIONS_MEM_ID: MemoryId = MemoryId::new(33);
+const NOTIFICATION_DEDUPE_IDX_MEM_ID: MemoryId = MemoryId::new(34);
+const RECOVERY_QR_MEM_ID: MemoryId = MemoryId::new(35);
+const OWNER_REBIND_MEM_ID: MemoryId = MemoryId::new(36);
+const EMERGENCY_ACCESS_MEM_ID: MemoryId = MemoryId::new(37);
+const TIME_CAPSULES_MEM_ID: MemoryId = MemoryId::new(38);
+const RECEIPTS_MEM_ID: MemoryId = MemoryId::new(39);
+const DELETION_JOBS_MEM_ID: MemoryId = MemoryId::new(40);
+const VAULT_TOMBSTONES_MEM_ID: MemoryId = MemoryId::new(41);
+const CONTENT_TRASH_MEM_ID: MemoryId = MemoryId::new(42);
+const CONTENT_VERSIONS_MEM_ID: MemoryId = MemoryId::new(43);
+const DRAFT_EXPIRY_DAYS_MEM_ID: MemoryId = MemoryId::new(44);
+const TRUSTED_AUTHORITIES_MEM_ID: MemoryId = MemoryId::new(45);
+const ATTESTATIONS_MEM_ID: MemoryId = MemoryId::new(46);
+const NOTIFICATION_OUTSTANDING_IDX_MEM_ID: MemoryId = MemoryId::new(47);
+const TOKEN_VAULT_IDX_MEM_ID: MemoryId = MemoryId::new(48);
+const NOTIFICATION_VAULT_IDX_MEM_ID: MemoryId = MemoryId::new(49);
+const TIME_CAPSULE_VAULT_IDX_MEM_ID: MemoryId = MemoryId::new(50);
+const CONTENT_TRASH_VAULT_IDX_MEM_ID: MemoryId = MemoryId::new(51);
 
 // Define memory type alias
 pub type Memory = VirtualMemory<DefaultMemoryImpl>;
@@ -149,3 +171,69 @@ pub fn get_staging_buffer_memory() -> Memory {
 pub fn get_cursor_memory() -> Memory {
     get_memory(CURSOR_MEM_ID)
 }
+pub fn get_owner_activity_memory() -> Memory {
+    get_memory(OWNER_ACTIVITY_MEM_ID)
+}
+pub fn get_inactivity_warnings_memory() -> Memory {
+    get_memory(INACTIVITY_WARNINGS_MEM_ID)
+}
+pub fn get_notification_counter_memory() -> Memory {
+    get_memory(NOTIFICATION_COUNTER_MEM_ID)
+}
+pub fn get_notifications_memory() -> Memory {
+    get_memory(NOTIFICATIONS_MEM_ID)
+}
+pub fn get_notification_dedupe_idx_memory() -> Memory {
+    get_memory(NOTIFICATION_DEDUPE_IDX_MEM_ID)
+}
+pub fn get_notification_outstanding_idx_memory() -> Memory {
+    get_memory(NOTIFICATION_OUTSTANDING_IDX_MEM_ID)
+}
+pub fn get_token_vault_idx_memory() -> Memory {
+    get_memory(TOKEN_VAULT_IDX_MEM_ID)
+}
+pub fn get_notification_vault_idx_memory() -> Memory {
+    get_memory(NOTIFICATION_VAULT_IDX_MEM_ID)
+}
+pub fn get_time_capsule_vault_idx_memory() -> Memory {
+    get_memory(TIME_CAPSULE_VAULT_IDX_MEM_ID)
+}
+pub fn get_content_trash_vault_idx_memory() -> Memory {
+    get_memory(CONTENT_TRASH_VAULT_IDX_MEM_ID)
+}
+pub fn get_recovery_qr_memory() -> Memory {
+    get_memory(RECOVERY_QR_MEM_ID)
+}
+pub fn get_owner_rebind_memory() -> Memory {
+    get_memory(OWNER_REBIND_MEM_ID)
+}
+pub fn get_emergency_access_memory() -> Memory {
+    get_memory(EMERGENCY_ACCESS_MEM_ID)
+}
+pub fn get_time_capsules_memory() -> Memory {
+    get_memory(TIME_CAPSULES_MEM_ID)
+}
+pub fn get_receipts_memory() -> Memory {
+    get_memory(RECEIPTS_MEM_ID)
+}
+pub fn get_deletion_jobs_memory() -> Memory {
+    get_memory(DELETION_JOBS_MEM_ID)
+}
+pub fn get_vault_tombstones_memory() -> Memory {
+    get_memory(VAULT_TOMBSTONES_MEM_ID)
+}
+pub fn get_content_trash_memory() -> Memory {
+    get_memory(CONTENT_TRASH_MEM_ID)
+}
+pub fn get_content_versions_memory() -> Memory {
+    get_memory(CONTENT_VERSIONS_MEM_ID)
+}
+pub fn get_draft_expiry_days_memory() -> Memory {
+    get_memory(DRAFT_EXPIRY_DAYS_MEM_ID)
+}
+pub fn get_trusted_authorities_memory() -> Memory {
+    get_memory(TRUSTED_AUTHORITIES_MEM_ID)
+}
+pub fn get_attestations_memory() -> Memory {
+    get_memory(ATTESTATIONS_MEM_ID)
+}
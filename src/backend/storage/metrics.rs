@@ -58,4 +58,18 @@ pub fn update_active_vault_count(delta: i64) -> Result<(), String> {
             metrics.active_vaults = metrics.active_vaults.saturating_sub(delta.abs() as u32);
         }
     })
-}
\ No newline at end of file
+}
+
+/// Records Draft vaults removed because their payment never completed.
+pub fn record_drafts_expired(count: u64) -> Result<(), String> {
+    update_metrics(|metrics| {
+        metrics.drafts_expired_total = metrics.drafts_expired_total.saturating_add(count);
+    })
+}
+
+/// Records payment sessions that timed out before confirmation.
+pub fn record_payment_sessions_expired(count: u64) -> Result<(), String> {
+    update_metrics(|metrics| {
+        metrics.payment_sessions_expired_total = metrics.payment_sessions_expired_total.saturating_add(count);
+    })
+}
@@ -18,6 +18,18 @@ pub mod metrics;
 pub mod billing;
 pub mod content_index;
 pub mod approvals; // Added approvals module
+pub mod activity;
+pub mod inactivity_warnings;
+pub mod notifications;
+pub mod recovery_qr;
+pub mod owner_recovery;
+pub mod emergency_access;
+pub mod time_capsules;
+pub mod receipts;
+pub mod deletion_jobs;
+pub mod content_trash;
+pub mod content_versions;
+pub mod attestations;
 
 // Re-export key storage structures and functions for easier access
 pub use cursor::{get_cursor, increment_cursor, set_cursor};
@@ -31,6 +43,7 @@ pub use uploads::{ get_next_upload_id, insert_upload_session, get_upload_session
 pub use members::{ insert_member, get_member, remove_member, get_members_by_vault, is_member, get_vaults_by_member, is_member_with_role, remove_members_by_vault };
 pub use config::{ get_admin_principal, get_cron_principal, get_min_cycles_threshold }; // Re-export config getters
 pub use vault_configs::{ insert_vault_config, get_vault_config, remove_vault_config, get_vaults_config_by_owner };
-pub use audit_logs::{add_entry as add_audit_log_entry, get_entries as get_audit_log_entries, compact_log as compact_audit_log, remove_audit_logs as remove_audit_logs };
+pub use audit_logs::{add_entry as add_audit_log_entry, get_entries as get_audit_log_entries, compact_log as compact_audit_log, remove_audit_logs_batch };
 pub use metrics::{ get_metrics, update_metrics };
-pub use billing::{ add_billing_entry, get_all_billing_entries, query_billing_entries };
\ No newline at end of file
+pub use billing::{ add_billing_entry, get_all_billing_entries, query_billing_entries };
+pub use activity::{ add_activity, get_activity, remove_activity_batch };
\ No newline at end of file
@@ -0,0 +1,214 @@
+// src/backend/storage/notifications.rs
+use crate::error::VaultError;
+use crate::models::common::{Timestamp, VaultId};
+use crate::models::notification::{NotificationEvent, NotificationId, NotificationStatus};
+use crate::storage::memory::{
+    get_notification_counter_memory, get_notification_dedupe_idx_memory, get_notification_outstanding_idx_memory,
+    get_notification_vault_idx_memory, get_notifications_memory, Memory,
+};
+use crate::storage::storable::{Cbor, StorableString};
+use ic_stable_structures::{StableBTreeMap, StableCell};
+use std::cell::RefCell;
+
+type StorableNotification = Cbor<NotificationEvent>;
+
+thread_local! {
+    // Counter for generating notification IDs (monotonic, so IDs double as an outbox cursor)
+    static NOTIFICATION_COUNTER: RefCell<StableCell<u64, Memory>> = RefCell::new(
+        StableCell::init(get_notification_counter_memory(), 1)
+            .expect("Failed to initialize notification counter")
+    );
+
+    // Primary storage: NotificationId -> Event
+    static NOTIFICATIONS: RefCell<StableBTreeMap<NotificationId, StorableNotification, Memory>> = RefCell::new(
+        StableBTreeMap::init(get_notifications_memory())
+    );
+
+    // Secondary index: Dedupe key -> NotificationId
+    static DEDUPE_INDEX: RefCell<StableBTreeMap<StorableString, NotificationId, Memory>> = RefCell::new(
+        StableBTreeMap::init(get_notification_dedupe_idx_memory())
+    );
+
+    // Secondary index: IDs of Pending/Delivered events, so pulls skip finished ones
+    static OUTSTANDING_INDEX: RefCell<StableBTreeMap<NotificationId, (), Memory>> = RefCell::new(
+        StableBTreeMap::init(get_notification_outstanding_idx_memory())
+    );
+
+    // Secondary index: (VaultId, NotificationId) -> (), so a vault's events can be purged by range
+    static VAULT_INDEX: RefCell<StableBTreeMap<(VaultId, NotificationId), (), Memory>> = RefCell::new(
+        StableBTreeMap::init(get_notification_vault_idx_memory())
+    );
+}
+
+fn is_outstanding(status: NotificationStatus) -> bool {
+    matches!(status, NotificationStatus::Pending | NotificationStatus::Delivered)
+}
+
+/// Adds or drops the event in the outstanding index according to its status.
+fn index_outstanding(id: NotificationId, status: NotificationStatus) {
+    OUTSTANDING_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        if is_outstanding(status) {
+            index.insert(id, ());
+        } else {
+            index.remove(&id);
+        }
+    });
+}
+
+/// Gets the next available notification ID and increments the counter.
+pub fn get_next_notification_id() -> Result<NotificationId, VaultError> {
+    NOTIFICATION_COUNTER.with(|cell_ref| {
+        let mut cell = cell_ref.borrow_mut();
+        let current_val = *cell.get();
+        let next_val = current_val.checked_add(1)
+            .ok_or_else(|| VaultError::InternalError("Notification counter overflow".to_string()))?;
+        cell.set(next_val)
+            .map_err(|e| VaultError::StorageError(format!("Failed to update notification counter: {:?}", e)))?;
+        Ok(current_val)
+    })
+}
+
+/// Looks up the event already enqueued under a dedupe key, if any.
+pub fn get_id_by_dedupe_key(dedupe_key: &str) -> Option<NotificationId> {
+    DEDUPE_INDEX.with(|index_ref| index_ref.borrow().get(&Cbor(dedupe_key.to_string())))
+}
+
+/// Inserts an event into both the primary map and the dedupe index.
+pub fn insert_notification(event: NotificationEvent) {
+    let id = event.id;
+    let key = Cbor(event.dedupe_key.clone());
+    index_outstanding(id, event.status);
+    VAULT_INDEX.with(|index_ref| {
+        index_ref.borrow_mut().insert((event.vault_id, id), ());
+    });
+    NOTIFICATIONS.with(|map_ref| {
+        map_ref.borrow_mut().insert(id, Cbor(event));
+    });
+    DEDUPE_INDEX.with(|index_ref| {
+        index_ref.borrow_mut().insert(key, id);
+    });
+}
+
+/// Overwrites an existing event (status and delivery bookkeeping).
+pub fn update_notification(event: NotificationEvent) {
+    index_outstanding(event.id, event.status);
+    NOTIFICATIONS.with(|map_ref| {
+        map_ref.borrow_mut().insert(event.id, Cbor(event));
+    });
+}
+
+/// Retrieves an event by ID.
+pub fn get_notification(id: NotificationId) -> Option<NotificationEvent> {
+    NOTIFICATIONS.with(|map_ref| map_ref.borrow().get(&id).map(|c| c.0))
+}
+
+/// Returns up to `limit` outstanding (Pending/Delivered) events with ID >= `start` that match
+/// `filter`, in ID order. Only the outstanding index is walked, never finished events.
+pub fn get_outstanding_from<F>(start: NotificationId, limit: usize, filter: F) -> Vec<NotificationEvent>
+where
+    F: Fn(&NotificationEvent) -> bool,
+{
+    OUTSTANDING_INDEX.with(|index_ref| {
+        NOTIFICATIONS.with(|map_ref| {
+            let map = map_ref.borrow();
+            index_ref.borrow()
+                .range(start..)
+                .filter_map(|(id, _)| map.get(&id).map(|c| c.0))
+                .filter(|event| filter(event))
+                .take(limit)
+                .collect()
+        })
+    })
+}
+
+/// Removes an event from the primary map and all three indexes.
+fn remove_event(id: NotificationId) {
+    let removed = NOTIFICATIONS.with(|map_ref| map_ref.borrow_mut().remove(&id));
+    OUTSTANDING_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&id));
+    if let Some(event) = removed {
+        VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&(event.0.vault_id, id)));
+        DEDUPE_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&Cbor(event.0.dedupe_key)));
+    }
+}
+
+/// Removes up to `limit` events of a vault, whatever their status (deletion jobs).
+/// Returns the number removed; 0 once the vault has no events left.
+pub fn remove_vault_notifications_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    let ids: Vec<NotificationId> = VAULT_INDEX.with(|index_ref| {
+        index_ref.borrow()
+            .range((*vault_id, 0)..=(*vault_id, NotificationId::MAX))
+            .take(limit)
+            .map(|((_, id), _)| id)
+            .collect()
+    });
+    for id in &ids {
+        remove_event(*id);
+        // Drops the entry even if the event itself was already gone
+        VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&(*vault_id, *id)));
+    }
+    ids.len() as u64
+}
+
+/// Rebuilds the vault index from the events (after an upgrade that introduced it).
+/// Does nothing when the index already has entries.
+pub fn rebuild_vault_index() -> u64 {
+    if VAULT_INDEX.with(|index_ref| !index_ref.borrow().is_empty()) {
+        return 0;
+    }
+    let keys: Vec<(VaultId, NotificationId)> = NOTIFICATIONS.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .map(|(id, c)| (c.0.vault_id, id))
+            .collect()
+    });
+    VAULT_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        for key in &keys {
+            index.insert(*key, ());
+        }
+    });
+    keys.len() as u64
+}
+
+/// Rebuilds the outstanding index from the events (after an upgrade that introduced it).
+/// Does nothing when the index already has entries.
+pub fn rebuild_outstanding_index() -> u64 {
+    if OUTSTANDING_INDEX.with(|index_ref| !index_ref.borrow().is_empty()) {
+        return 0;
+    }
+    let ids: Vec<NotificationId> = NOTIFICATIONS.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .filter(|(_, c)| is_outstanding(c.0.status))
+            .map(|(id, _)| id)
+            .collect()
+    });
+    OUTSTANDING_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        for id in &ids {
+            index.insert(*id, ());
+        }
+    });
+    ids.len() as u64
+}
+
+/// Removes acked and failed events created before `cutoff`, along with their dedupe entries.
+/// Returns the number of events removed.
+pub fn prune_finished(cutoff: Timestamp) -> u64 {
+    let finished: Vec<NotificationId> = NOTIFICATIONS.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .filter(|(_, c)| {
+                c.0.created_at < cutoff
+                    && matches!(c.0.status, NotificationStatus::Acked | NotificationStatus::Failed)
+            })
+            .map(|(id, _)| id)
+            .collect()
+    });
+
+    for id in &finished {
+        remove_event(*id);
+    }
+    finished.len() as u64
+}
@@ -0,0 +1,48 @@
+// src/backend/storage/owner_recovery.rs
+use crate::models::common::VaultId;
+use crate::models::owner_recovery::OwnerRebindRequest;
+use crate::storage::memory::{get_owner_rebind_memory, Memory};
+use crate::storage::storable::Cbor;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+type RebindMap = StableBTreeMap<VaultId, Cbor<OwnerRebindRequest>, Memory>;
+
+thread_local! {
+    /// Stable storage for the latest owner rebind request of each vault.
+    /// Key: VaultId (Principal)
+    /// Value: Cbor<OwnerRebindRequest> (a new request replaces a resolved one)
+    static REBIND_REQUESTS: RefCell<RebindMap> = RefCell::new(
+        RebindMap::init(get_owner_rebind_memory())
+    );
+}
+
+/// Inserts or replaces the rebind request for a vault.
+pub fn insert_rebind_request(request: OwnerRebindRequest) {
+    REBIND_REQUESTS.with(|map_ref| {
+        map_ref.borrow_mut().insert(request.vault_id, Cbor(request));
+    });
+}
+
+/// Retrieves the latest rebind request for a vault.
+pub fn get_rebind_request(vault_id: &VaultId) -> Option<OwnerRebindRequest> {
+    REBIND_REQUESTS.with(|map_ref| map_ref.borrow().get(vault_id).map(|c| c.0))
+}
+
+/// Returns all requests that are still open (pending or approved).
+/// NOTE: This iterates the entire map, which can be inefficient.
+pub fn get_open_rebind_requests() -> Vec<OwnerRebindRequest> {
+    REBIND_REQUESTS.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .map(|(_, c)| c.0)
+            .filter(|r| r.is_open())
+            .collect()
+    })
+}
+
+/// Removes the rebind request for a vault during deletion.
+/// Returns the number removed (0 or 1).
+pub fn remove_rebind_request(vault_id: &VaultId) -> u64 {
+    REBIND_REQUESTS.with(|map_ref| map_ref.borrow_mut().remove(vault_id).is_some() as u64)
+}
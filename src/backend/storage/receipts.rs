@@ -0,0 +1,68 @@
+// src/backend/storage/receipts.rs
+use crate::models::common::{ContentId, PrincipalId, VaultId};
+use crate::models::receipt::ContentReceipt;
+use crate::storage::memory::{get_receipts_memory, Memory};
+use crate::storage::storable::Cbor;
+use candid::Principal;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+type ReceiptsMap = StableBTreeMap<(VaultId, PrincipalId), Cbor<Vec<ContentReceipt>>, Memory>;
+
+thread_local! {
+    /// Download/acknowledgement receipts per vault member.
+    /// Key: (VaultId, member PrincipalId)
+    /// Value: Cbor<Vec<ContentReceipt>> (one entry per content item)
+    static RECEIPTS: RefCell<ReceiptsMap> = RefCell::new(
+        ReceiptsMap::init(get_receipts_memory())
+    );
+}
+
+/// Applies `update` to the member's receipt for `content_id`, creating an empty one first if needed.
+pub fn update_receipt<F: FnOnce(&mut ContentReceipt)>(vault_id: &VaultId, member: &PrincipalId, content_id: &ContentId, update: F) -> ContentReceipt {
+    RECEIPTS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let key = (*vault_id, *member);
+        let mut receipts = map.get(&key).map(|c| c.0).unwrap_or_default();
+        let index = match receipts.iter().position(|r| r.content_id == *content_id) {
+            Some(i) => i,
+            None => {
+                receipts.push(ContentReceipt {
+                    content_id: *content_id,
+                    first_downloaded_at: None,
+                    last_downloaded_at: None,
+                    download_count: 0,
+                    acknowledged_at: None,
+                });
+                receipts.len() - 1
+            }
+        };
+        update(&mut receipts[index]);
+        let receipt = receipts[index].clone();
+        map.insert(key, Cbor(receipts));
+        receipt
+    })
+}
+
+/// Retrieves all receipts of a member in a vault.
+pub fn get_receipts(vault_id: &VaultId, member: &PrincipalId) -> Vec<ContentReceipt> {
+    RECEIPTS.with(|map_ref| map_ref.borrow().get(&(*vault_id, *member)).map(|c| c.0).unwrap_or_default())
+}
+
+/// Removes up to `limit` members' receipts of a vault (deletion jobs).
+/// Returns the number removed; 0 once the vault has no receipts left.
+pub fn remove_vault_receipts_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    RECEIPTS.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        let keys: Vec<(VaultId, PrincipalId)> = map
+            .range((*vault_id, Principal::management_canister())..)
+            .take_while(|((vid, _), _)| vid == vault_id)
+            .take(limit)
+            .map(|(key, _)| key)
+            .collect();
+        for key in &keys {
+            map.remove(key);
+        }
+        keys.len() as u64
+    })
+}
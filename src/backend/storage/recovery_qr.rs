@@ -0,0 +1,36 @@
+// src/backend/storage/recovery_qr.rs
+use crate::models::common::VaultId;
+use crate::models::recovery_qr::RecoveryQrRecord;
+use crate::storage::memory::{get_recovery_qr_memory, Memory};
+use crate::storage::storable::Cbor;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+type RecoveryQrMap = StableBTreeMap<VaultId, Cbor<RecoveryQrRecord>, Memory>;
+
+thread_local! {
+    /// Stable storage for the current recovery QR of each vault.
+    /// Key: VaultId (Principal)
+    /// Value: Cbor<RecoveryQrRecord> (one per vault; re-issuing replaces it)
+    static RECOVERY_QR: RefCell<RecoveryQrMap> = RefCell::new(
+        RecoveryQrMap::init(get_recovery_qr_memory())
+    );
+}
+
+/// Inserts or replaces the recovery QR record for a vault.
+pub fn insert_recovery_qr(record: RecoveryQrRecord) {
+    RECOVERY_QR.with(|map_ref| {
+        map_ref.borrow_mut().insert(record.vault_id, Cbor(record));
+    });
+}
+
+/// Retrieves the recovery QR record for a vault.
+pub fn get_recovery_qr(vault_id: &VaultId) -> Option<RecoveryQrRecord> {
+    RECOVERY_QR.with(|map_ref| map_ref.borrow().get(vault_id).map(|c| c.0))
+}
+
+/// Removes the recovery QR record for a vault during deletion.
+/// Returns the number removed (0 or 1).
+pub fn remove_recovery_qr(vault_id: &VaultId) -> u64 {
+    RECOVERY_QR.with(|map_ref| map_ref.borrow_mut().remove(vault_id).is_some() as u64)
+}
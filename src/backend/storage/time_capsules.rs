@@ -0,0 +1,106 @@
+// src/backend/storage/time_capsules.rs
+use crate::models::common::{ContentId, Timestamp, VaultId};
+use crate::storage::memory::{get_time_capsules_memory, get_time_capsule_vault_idx_memory, Memory};
+use candid::Principal;
+use ic_stable_structures::StableBTreeMap;
+use std::cell::RefCell;
+
+// Key: (release_at, ContentId), so due capsules are a prefix scan
+// Value: VaultId of the content item
+type CapsuleMap = StableBTreeMap<(Timestamp, ContentId), VaultId, Memory>;
+// Secondary index: (VaultId, ContentId) -> release_at, for per-vault range scans
+type VaultIndex = StableBTreeMap<(VaultId, ContentId), Timestamp, Memory>;
+
+thread_local! {
+    /// Schedule of unreleased time-capsule content items, ordered by release time.
+    /// The item itself (recipients, release state) lives in content storage.
+    static CAPSULES: RefCell<CapsuleMap> = RefCell::new(
+        CapsuleMap::init(get_time_capsules_memory())
+    );
+
+    static CAPSULE_VAULT_INDEX: RefCell<VaultIndex> = RefCell::new(
+        VaultIndex::init(get_time_capsule_vault_idx_memory())
+    );
+}
+
+/// Schedules (or reschedules) a content item's release.
+pub fn schedule(release_at: Timestamp, content_id: ContentId, vault_id: VaultId) {
+    CAPSULES.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        unschedule_in(&mut map, &content_id);
+        map.insert((release_at, content_id), vault_id);
+    });
+    CAPSULE_VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().insert((vault_id, content_id), release_at));
+}
+
+/// Removes a content item from the schedule, if present.
+pub fn unschedule(content_id: &ContentId) {
+    CAPSULES.with(|map_ref| unschedule_in(&mut map_ref.borrow_mut(), content_id));
+}
+
+fn unschedule_in(map: &mut CapsuleMap, content_id: &ContentId) {
+    let keys: Vec<(Timestamp, ContentId)> = map.iter().map(|(k, _)| k).filter(|(_, c)| c == content_id).collect();
+    for key in keys {
+        if let Some(vault_id) = map.remove(&key) {
+            CAPSULE_VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&(vault_id, *content_id)));
+        }
+    }
+}
+
+/// Returns the scheduled items due at `current_time` (release_at <= current_time).
+pub fn get_due(current_time: Timestamp) -> Vec<(ContentId, VaultId)> {
+    CAPSULES.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .take_while(|((release_at, _), _)| *release_at <= current_time)
+            .map(|((_, content_id), vault_id)| (content_id, vault_id))
+            .collect()
+    })
+}
+
+/// Removes up to `limit` scheduled items of a vault via the vault index (deletion jobs).
+/// Returns the number removed; 0 once the vault has none left.
+pub fn remove_vault_capsules_batch(vault_id: &VaultId, limit: usize) -> u64 {
+    let entries: Vec<(ContentId, Timestamp)> = CAPSULE_VAULT_INDEX.with(|index_ref| {
+        index_ref.borrow()
+            .range((*vault_id, Principal::management_canister())..)
+            .take_while(|((vid, _), _)| vid == vault_id)
+            .take(limit)
+            .map(|((_, content_id), release_at)| (content_id, release_at))
+            .collect()
+    });
+    CAPSULES.with(|map_ref| {
+        let mut map = map_ref.borrow_mut();
+        for (content_id, release_at) in &entries {
+            map.remove(&(*release_at, *content_id));
+        }
+    });
+    CAPSULE_VAULT_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        for (content_id, _) in &entries {
+            index.remove(&(*vault_id, *content_id));
+        }
+    });
+    entries.len() as u64
+}
+
+/// Rebuilds the per-vault index from the scheduled items (after an upgrade that introduced it).
+/// Does nothing when the index already has entries.
+pub fn rebuild_vault_index() -> u64 {
+    if CAPSULE_VAULT_INDEX.with(|index_ref| !index_ref.borrow().is_empty()) {
+        return 0;
+    }
+    let keys: Vec<((VaultId, ContentId), Timestamp)> = CAPSULES.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .map(|((release_at, content_id), vault_id)| ((vault_id, content_id), release_at))
+            .collect()
+    });
+    CAPSULE_VAULT_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        for (key, release_at) in &keys {
+            index.insert(*key, *release_at);
+        }
+    });
+    keys.len() as u64
+}
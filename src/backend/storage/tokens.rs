@@ -1,12 +1,12 @@
 // src/backend/storage/tokens.rs
 use crate::error::VaultError;
-use crate::models::vault_invite_token::VaultInviteToken;
+use crate::models::vault_invite_token::{TokenStatus, VaultInviteToken};
 use crate::storage::storable::Cbor;
-use crate::storage::memory::{Memory, get_token_counter_memory, get_invite_tokens_memory, get_token_principal_idx_memory};
+use crate::storage::memory::{Memory, get_token_counter_memory, get_invite_tokens_memory, get_token_principal_idx_memory, get_token_vault_idx_memory};
 use ic_stable_structures::{StableCell, StableBTreeMap};
 use std::cell::RefCell;
 use candid::Principal;
-use crate::models::common::VaultId;
+use crate::models::common::{Timestamp, VaultId};
 
 type StorableToken = Cbor<VaultInviteToken>;
 type PrincipalBytes = Vec<u8>; // Key for secondary index
@@ -27,6 +27,11 @@ thread_local! {
     static TOKEN_PRINCIPAL_INDEX: RefCell<StableBTreeMap<PrincipalBytes, u64, Memory>> = RefCell::new(
         StableBTreeMap::init(get_token_principal_idx_memory()) // Use new memory ID for the index
     );
+
+    // Secondary index: (VaultId, Internal u64 ID) -> (), for per-vault range scans
+    static TOKEN_VAULT_INDEX: RefCell<StableBTreeMap<(VaultId, u64), (), Memory>> = RefCell::new(
+        StableBTreeMap::init(get_token_vault_idx_memory())
+    );
 }
 
 /// Gets the next available internal token ID and increments the counter.
@@ -45,6 +50,7 @@ pub fn get_next_token_id() -> Result<u64, VaultError> {
 
 /// Inserts a token into both the primary map and the secondary index.
 pub fn insert_token(internal_id: u64, token: VaultInviteToken, principal_id: Principal) -> Result<(), VaultError> {
+    let vault_id = token.vault_id;
     let storable_token = Cbor(token);
     let principal_bytes = principal_id.as_slice().to_vec();
 
@@ -59,6 +65,9 @@ pub fn insert_token(internal_id: u64, token: VaultInviteToken, principal_id: Pri
          // insert returns Option<V>
          index_ref.borrow_mut().insert(principal_bytes, internal_id);
     });
+    TOKEN_VAULT_INDEX.with(|index_ref| {
+        index_ref.borrow_mut().insert((vault_id, internal_id), ());
+    });
     // Assuming insertion errors trap, otherwise map Result/Option
     Ok(())
 }
@@ -75,11 +84,46 @@ pub fn get_internal_token_id(principal: Principal) -> Option<u64> {
     })
 }
 
+/// Returns issued (unclaimed) tokens whose expiry falls within `[from, to)`.
+/// NOTE: This iterates the entire map, which can be inefficient.
+pub fn get_tokens_expiring_between(from: Timestamp, to: Timestamp) -> Vec<VaultInviteToken> {
+    TOKENS_MAP.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .map(|(_, c)| c.0)
+            .filter(|t| t.status == TokenStatus::Issued && t.expires_at >= from && t.expires_at < to)
+            .collect()
+    })
+}
+
+/// Internal IDs of a vault's tokens, in issue order, from the per-vault index.
+fn vault_token_ids(vault_id: &VaultId) -> Vec<u64> {
+    TOKEN_VAULT_INDEX.with(|index_ref| {
+        index_ref.borrow()
+            .range((*vault_id, 0)..=(*vault_id, u64::MAX))
+            .map(|((_, internal_id), _)| internal_id)
+            .collect()
+    })
+}
+
+/// Returns all tokens of a vault, whatever their status.
+pub fn get_tokens_by_vault(vault_id: &VaultId) -> Vec<VaultInviteToken> {
+    let ids = vault_token_ids(vault_id);
+    TOKENS_MAP.with(|map_ref| {
+        let map = map_ref.borrow();
+        ids.into_iter().filter_map(|id| map.get(&id).map(|c| c.0)).collect()
+    })
+}
+
 /// Removes a token from both the primary map and the secondary index.
 pub fn remove_token(internal_id: u64, principal_id: Principal) -> Result<(), VaultError> {
     // Remove from primary map
     let removed_token = TOKENS_MAP.with(|map_ref| map_ref.borrow_mut().remove(&internal_id));
 
+    if let Some(token) = &removed_token {
+        TOKEN_VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&(token.0.vault_id, internal_id)));
+    }
+
     // Optional: Check if the token actually existed
     if removed_token.is_none() {
         // Depending on requirements, log a warning or return an error
@@ -135,4 +179,47 @@ pub async fn remove_tokens_by_vault(vault_id: &VaultId) -> Result<u64, VaultErro
             errors.join("; ")
         )))
     }
-} 
\ No newline at end of file
+} 
+/// Removes up to `limit` invite tokens of a vault (deletion jobs).
+/// Returns the number removed; 0 once the vault has no tokens left.
+pub fn remove_tokens_batch(vault_id: &VaultId, limit: usize) -> Result<u64, VaultError> {
+    let ids: Vec<u64> = TOKEN_VAULT_INDEX.with(|index_ref| {
+        index_ref.borrow()
+            .range((*vault_id, 0)..=(*vault_id, u64::MAX))
+            .take(limit)
+            .map(|((_, internal_id), _)| internal_id)
+            .collect()
+    });
+
+    for internal_id in &ids {
+        match get_token(*internal_id) {
+            Some(token) => remove_token(*internal_id, token.token_id)?,
+            // Dangling index entry; drop it so the batch makes progress
+            None => {
+                TOKEN_VAULT_INDEX.with(|index_ref| index_ref.borrow_mut().remove(&(*vault_id, *internal_id)));
+            }
+        }
+    }
+    Ok(ids.len() as u64)
+}
+
+/// Rebuilds the per-vault token index from the tokens (after an upgrade that introduced it).
+/// Does nothing when the index already has entries.
+pub fn rebuild_vault_index() -> u64 {
+    if TOKEN_VAULT_INDEX.with(|index_ref| !index_ref.borrow().is_empty()) {
+        return 0;
+    }
+    let keys: Vec<(VaultId, u64)> = TOKENS_MAP.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .map(|(internal_id, token)| (token.0.vault_id, internal_id))
+            .collect()
+    });
+    TOKEN_VAULT_INDEX.with(|index_ref| {
+        let mut index = index_ref.borrow_mut();
+        for key in &keys {
+            index.insert(*key, ());
+        }
+    });
+    keys.len() as u64
+}
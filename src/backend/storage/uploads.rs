@@ -2,6 +2,7 @@
 // Manages storage related to file upload sessions.
 
 use crate::error::VaultError;
+use crate::models::common::VaultId;
 use crate::models::upload_session::UploadSession;
 use crate::storage::storable::Cbor;
 use crate::storage::memory::{Memory, get_upload_session_memory, get_upload_counter_memory, get_upload_principal_idx_memory, get_upload_chunks_memory};
@@ -133,3 +134,32 @@ pub fn delete_chunks(internal_upload_id: u64) -> Result<(), VaultError> {
     });
     Ok(())
 }
+
+/// Removes up to `limit` upload sessions of a vault together with their chunks (deletion jobs).
+/// Returns the number of sessions and chunks removed; 0 once the vault has no sessions left.
+pub fn remove_uploads_batch(vault_id: &VaultId, limit: usize) -> Result<u64, VaultError> {
+    let sessions: Vec<(u64, Principal)> = UPLOAD_SESSIONS_MAP.with(|map_ref| {
+        map_ref.borrow()
+            .iter()
+            .filter(|(_, session)| session.0.vault_id == *vault_id)
+            .take(limit)
+            .map(|(internal_id, session)| (internal_id, session.0.upload_id))
+            .collect()
+    });
+
+    let mut removed = 0u64;
+    for (internal_id, upload_id) in &sessions {
+        let chunk_keys: Vec<(u64, u64)> = UPLOAD_CHUNKS_MAP.with(|map_ref| {
+            map_ref.borrow().range((*internal_id, 0)..=(*internal_id, u64::MAX)).map(|(key, _)| key).collect()
+        });
+        UPLOAD_CHUNKS_MAP.with(|map_ref| {
+            let mut map = map_ref.borrow_mut();
+            for key in &chunk_keys {
+                map.remove(key);
+            }
+        });
+        remove_upload_session(*internal_id, *upload_id)?;
+        removed += 1 + chunk_keys.len() as u64;
+    }
+    Ok(removed)
+}
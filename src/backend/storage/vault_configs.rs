@@ -1,6 +1,7 @@
 // src/backend/storage/vault_configs.rs
 use crate::storage::memory::{get_vault_config_memory, Memory};
 use crate::storage::storable::{Cbor, StorableString};
+use crate::error::VaultError;
 use crate::models::{common::VaultId, vault_config::VaultConfig, PrincipalId};
 use ic_stable_structures::StableBTreeMap;
 use std::cell::RefCell;
@@ -38,13 +39,24 @@ pub fn get_vault_config(vault_id: &VaultId) -> Option<VaultConfig> {
     })
 }
 
+/// Retrieves a vault configuration, or `VaultNotFound` if there is none.
+pub fn require(vault_id: &VaultId) -> Result<VaultConfig, VaultError> {
+    get_vault_config(vault_id).ok_or_else(|| VaultError::VaultNotFound(vault_id.to_string()))
+}
+
+/// True if the vault exists and is under legal hold. Scheduler jobs use this to leave held vaults alone.
+pub fn is_on_legal_hold(vault_id: &VaultId) -> bool {
+    get_vault_config(vault_id).map_or(false, |config| config.legal_hold.is_some())
+}
+
+/// Retrieves all vaults owned or co-owned by a principal (full scan).
 pub fn get_vaults_config_by_owner(owner: PrincipalId) -> Vec<VaultConfig> {
     let mut owned_vaults = Vec::new();
     CONFIGS.with(|map_ref| {
         let map = map_ref.borrow();
         for (_key, value) in map.iter() {
             let config: VaultConfig = value.0;
-            if config.owner == owner {
+            if config.is_owner(&owner) {
                 owned_vaults.push(config);
             }
         }
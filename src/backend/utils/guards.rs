@@ -2,7 +2,7 @@
 use crate::{
     error::VaultError,
     models::{
-        vault_config::VaultConfig,
+        vault_config::{ManagerPermission, VaultConfig},
         vault_member::{VaultMember, MemberStatus},
         common::{VaultId, PrincipalId, Role},
     },
@@ -58,6 +58,17 @@ pub fn admin_guard() -> Result<(), String> {
     }
 }
 
+/// Guard: Allow only the cron principal (the off-chain relay).
+pub fn cron_guard() -> Result<(), String> {
+    let caller = ic_caller();
+    let cron = storage_config::get_cron_principal();
+    if caller == cron {
+        Ok(())
+    } else {
+        Err(format!("Caller {} is not the cron trigger {}", caller, cron))
+    }
+}
+
 /// Guard: Allow only the cron principal OR the admin principal.
 pub fn cron_or_admin_guard() -> Result<(), String> {
     let caller = ic_caller();
@@ -70,14 +81,15 @@ pub fn cron_or_admin_guard() -> Result<(), String> {
     }
 }
 
-/// Guard: Check if caller is the owner of the specified vault.
+/// Guard: Check if caller is an owner (primary or co-owner) of the specified vault.
+/// Co-owners pass as well, so endpoints behind it must still check the specific `OwnerPermission`.
 /// NOTE: This requires fetching vault config, making it potentially expensive.
 /// Consider alternative designs if performance is critical.
 pub fn owner_guard(vault_id: VaultId) -> Result<(), String> {
     let caller = ic_caller();
     match storage::get_vault_config(&vault_id) {
         Some(config) => {
-            if config.owner == caller {
+            if config.is_owner(&caller) {
                 Ok(())
             } else {
                 Err(format!("Caller {} is not the owner of vault {}", caller, vault_id))
@@ -87,14 +99,44 @@ pub fn owner_guard(vault_id: VaultId) -> Result<(), String> {
     }
 }
 
+/// True if `principal` is a participating manager (`Role::Manager`) of the vault.
+pub fn is_manager(vault_id: &VaultId, principal: &PrincipalId) -> bool {
+    storage::get_member(vault_id, principal)
+        .map_or(false, |member| member.role == Role::Manager && member.is_participating())
+}
+
+/// True if `principal` may perform a delegated action: an owner holding the matching
+/// owner permission, or a manager of the vault.
+pub fn has_delegated_permission(config: &VaultConfig, principal: &PrincipalId, permission: ManagerPermission) -> bool {
+    config.has_owner_permission(principal, permission.owner_permission()) || is_manager(&config.vault_id, principal)
+}
+
+/// Guard: Check if caller is an owner or a manager of the specified vault.
+/// Endpoints behind it must still check the specific `ManagerPermission`.
+pub fn owner_or_manager_guard(vault_id: VaultId) -> Result<(), String> {
+    let caller = ic_caller();
+    match storage::get_vault_config(&vault_id) {
+        Some(config) => {
+            if config.is_owner(&caller) || is_manager(&vault_id, &caller) {
+                Ok(())
+            } else {
+                Err(format!("Caller {} is not an owner or manager of vault {}", caller, vault_id))
+            }
+        }
+        None => Err(format!("Vault {} not found for owner/manager check", vault_id)),
+    }
+}
+
 /// Guard: Checks if the caller is either the owner or a *verified* designated heir.
+/// Managers do not pass: they have no rights to content or unlock. Co-owners pass as owners;
+/// endpoints that change state must still check the specific `OwnerPermission`.
 pub fn owner_or_heir_guard(vault_id: VaultId) -> Result<(), String> {
     let caller = ic_caller();
 
     // 1. Check if caller is the owner
     match storage::get_vault_config(&vault_id) {
         Some(config) => {
-            if config.owner == caller {
+            if config.is_owner(&caller) {
                 return Ok(());
             }
             // If not owner, proceed to check if they are a verified heir
@@ -119,11 +161,13 @@ pub fn owner_or_heir_guard(vault_id: VaultId) -> Result<(), String> {
     }
 }
 
-/// Guard: Checks if the caller is a member (any role) of the specified vault.
+/// Guard: Checks if the caller is a participating heir or witness of the specified vault.
+/// Managers are members too but only pass the owner/manager and role guards.
 pub fn member_guard(vault_id: VaultId) -> Result<(), String> {
     let caller = ic_caller();
 
-    let is_member = storage::is_member(&vault_id, &caller);
+    let is_member = storage::get_member(&vault_id, &caller)
+        .map_or(false, |member| member.may_receive_content());
 
     if is_member {
         Ok(())
@@ -137,6 +181,7 @@ pub fn member_guard(vault_id: VaultId) -> Result<(), String> {
 }
 
 /// Guard: Checks if the caller is a member with the specified role in the vault.
+/// `Role::Manager` can be required here to admit managers only.
 pub fn role_guard(vault_id: VaultId, required_role: Role) -> Result<(), String> {
     let caller = ic_caller();
 
@@ -158,6 +203,7 @@ pub fn role_guard(vault_id: VaultId, required_role: Role) -> Result<(), String>
 }
 
 /// Placeholder Guard: Checks if the caller is the specified member principal or the vault owner.
+/// Any co-owner passes as the owner; check the specific `OwnerPermission` behind it.
 pub fn self_or_owner_guard(vault_id: VaultId, member_principal: PrincipalId) -> Result<(), String> {
      let caller = ic_caller();
     let vault_key = Cbor(vault_id.clone());
@@ -166,7 +212,7 @@ pub fn self_or_owner_guard(vault_id: VaultId, member_principal: PrincipalId) ->
         match map.get(&vault_key) {
             Some(config_cbor) => {
                 let config: VaultConfig = config_cbor.0;
-                if config.owner == caller || member_principal == caller {
+                if config.is_owner(&caller) || member_principal == caller {
                     Ok(())
                 } else {
                      Err(format!("Caller {} is not the owner or the specified principal {} for vault {}", caller, member_principal, vault_id))